@@ -0,0 +1,123 @@
+use show_message::UnwrapOrShow;
+use stats::{EnemyKind, RunStats};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::Cursor;
+use std::io::Read;
+use std::time::Duration;
+
+const FILENAME: &str = "assets/achievements.ron";
+
+lazy_static! {
+    pub static ref ACHIEVEMENTS: Vec<AchievementConf> = {
+        let file = if cfg!(feature = "packed") {
+            Box::new(Cursor::new(include_bytes!("../assets/achievements.ron").iter())) as Box<Read>
+        } else {
+            Box::new(File::open(FILENAME)
+                .unwrap_or_else_show(|e| format!("Failed to open achievements file at {}: {}", FILENAME, e)))
+                as Box<Read>
+        };
+
+        ::ron::de::from_reader(file)
+            .unwrap_or_else_show(|e| format!("Failed to parse achievements file {}: {}", FILENAME, e))
+    };
+}
+
+/// Achievement definition, its name and description are in the language files under its id
+#[derive(Deserialize)]
+pub struct AchievementConf {
+    pub id: String,
+    pub condition: Condition,
+}
+
+#[derive(Deserialize)]
+pub enum Condition {
+    /// Complete the level, under the time in seconds if any
+    Complete {
+        level: usize,
+        #[serde(default)]
+        time: Option<f32>,
+    },
+    /// Complete a level, or any level if none, with every shot killing
+    CompleteWithoutMiss {
+        #[serde(default)]
+        level: Option<usize>,
+    },
+    /// Complete a level, or any level if none, without using the hook
+    CompleteWithoutHook {
+        #[serde(default)]
+        level: Option<usize>,
+        /// Only levels with a 3D part count
+        #[serde(default)]
+        only_3d: bool,
+    },
+    /// Kill enemies of the kind over all runs
+    Kill {
+        kind: EnemyKind,
+        count: u32,
+    },
+}
+
+/// Level completed in the update and the statistics of the run
+pub struct Completion<'a> {
+    pub level: usize,
+    pub time: Duration,
+    pub stats: &'a RunStats,
+}
+
+impl Condition {
+    pub fn met(&self, progress: &AchievementProgress, completion: Option<&Completion>) -> bool {
+        match *self {
+            Condition::Complete { level, time } => completion
+                .map(|completion| {
+                    let seconds = completion.time.as_secs() as f32
+                        + completion.time.subsec_nanos() as f32 * 1e-9;
+                    completion.level == level && time.map(|time| seconds <= time).unwrap_or(true)
+                })
+                .unwrap_or(false),
+            Condition::CompleteWithoutMiss { level } => completion
+                .map(|completion| {
+                    level.map(|level| completion.level == level).unwrap_or(true)
+                        && completion.stats.shots > 0
+                        && completion.stats.hits == completion.stats.shots
+                })
+                .unwrap_or(false),
+            Condition::CompleteWithoutHook { level, only_3d } => completion
+                .map(|completion| {
+                    level.map(|level| completion.level == level).unwrap_or(true)
+                        && (!only_3d || ::CONFIG.levels[completion.level].parts.iter().any(|part| part.is_3d()))
+                        && completion.stats.hooks == 0
+                })
+                .unwrap_or(false),
+            Condition::Kill { kind, count } => progress.kills(kind) >= count,
+        }
+    }
+}
+
+/// Achievements unlocked and progress toward them, stored in the save
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AchievementProgress {
+    pub unlocked: BTreeSet<String>,
+    pub kills: BTreeMap<EnemyKind, u32>,
+}
+
+impl AchievementProgress {
+    pub fn kills(&self, kind: EnemyKind) -> u32 {
+        self.kills.get(&kind).cloned().unwrap_or(0)
+    }
+}
+
+/// Achievements unlocked during the game, shown in the HUD
+pub struct Achievements {
+    /// Id of the achievement and remaining duration of the notification
+    pub notifications: Vec<(String, f32)>,
+}
+
+impl Achievements {
+    pub fn new() -> Self {
+        Achievements {
+            notifications: vec![],
+        }
+    }
+}
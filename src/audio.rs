@@ -21,6 +21,12 @@ pub enum Sound {
     DepthBallBirthDeath,
     Eraser,
     Attracted,
+    SlowBallAttack,
+    BlindBallAttack,
+    InvertBallAttack,
+    PushBallAttack,
+    Pickup,
+    Door,
 }
 
 /// Sounds must be 44100 Hz and stereo
@@ -155,6 +161,7 @@ pub struct AudioSinkControl {
     right_ear: [f32; 3],
     effect_volume: f32,
     eraser_volume: f32,
+    speed: f32,
     spatial_sounds_to_add: Vec<(Sound, [f32; 3])>,
     sounds_to_add: Vec<Sound>,
 }
@@ -166,6 +173,7 @@ impl AudioSinkControl {
             right_ear: [0f32; 3],
             effect_volume: volume,
             eraser_volume: 0.0,
+            speed: 1.0,
             spatial_sounds_to_add: vec![],
             sounds_to_add: vec![],
         }
@@ -178,7 +186,8 @@ struct SoundBuffer {
 }
 
 impl SoundBuffer {
-    fn new(sound: Decoder<Cursor<Vec<u8>>>) -> Result<Self, String> {
+    /// Pitch is applied by resampling: 2.0 plays twice faster and an octave higher
+    fn new(sound: Decoder<Cursor<Vec<u8>>>, pitch: f32) -> Result<Self, String> {
         if sound.sample_rate() != 44100 {
             return Err("invalid samples rate: must be 44100 Hz".into());
         }
@@ -186,15 +195,32 @@ impl SoundBuffer {
             return Err("invalid channels: must be stereo".into());
         }
 
+        let samples = sound.collect::<Vec<_>>();
+        let samples = if pitch == 1.0 {
+            samples
+        } else {
+            let frames = samples.len() / 2;
+            let new_frames = (frames as f32 / pitch) as usize;
+            let mut resampled = Vec::with_capacity(new_frames * 2);
+            for i in 0..new_frames {
+                let frame = (i as f32 * pitch) as usize;
+                resampled.push(samples[frame * 2]);
+                resampled.push(samples[frame * 2 + 1]);
+            }
+            resampled
+        };
+
         Ok(SoundBuffer {
-            samples: Arc::new(sound.collect::<Vec<_>>()),
+            samples: Arc::new(samples),
         })
     }
 
-    fn source(&self) -> SoundSource {
+    fn source(&self, speed: f32) -> SoundSource {
         SoundSource {
             samples: self.samples.clone(),
-            cursor: 0,
+            cursor: 0.0,
+            speed,
+            right: false,
         }
     }
 
@@ -238,17 +264,24 @@ impl Source for InfiniteSoundSource {
     }
 }
 
-// sound soure from a 44100 Hz stereo buffer
+// sound soure from a 44100 Hz stereo buffer played at a given speed
 struct SoundSource {
     samples: Arc<Vec<i16>>,
-    cursor: usize,
+    /// Position in frames
+    cursor: f32,
+    speed: f32,
+    right: bool,
 }
 
 impl Iterator for SoundSource {
     type Item = i16;
     fn next(&mut self) -> Option<Self::Item> {
-        let sample = self.samples.get(self.cursor).cloned();
-        self.cursor += 1;
+        let frame = self.cursor as usize;
+        let sample = self.samples.get(frame * 2 + self.right as usize).cloned();
+        if self.right {
+            self.cursor += self.speed;
+        }
+        self.right = !self.right;
         sample
     }
 }
@@ -270,17 +303,24 @@ impl Source for SoundSource {
 
 lazy_static! {
     static ref SOUND_BUFFERS: Vec<SoundBuffer> = {
+        // Some sounds reuse another one at a different pitch
         let sound_filenames = [
-            "assets/sounds/shoot.ogg",
-            "assets/sounds/kill.ogg",
-            "assets/sounds/death.ogg",
-            "assets/sounds/all_killed.ogg",
-            "assets/sounds/portal.ogg",
-            "assets/sounds/bounce.ogg",
-            "assets/sounds/depth_ball_attack.ogg",
-            "assets/sounds/depth_ball_birth_death.ogg",
-            "assets/sounds/eraser.wav",
-            "assets/sounds/attracted.ogg",
+            ("assets/sounds/shoot.ogg", 1.0),
+            ("assets/sounds/kill.ogg", 1.0),
+            ("assets/sounds/death.ogg", 1.0),
+            ("assets/sounds/all_killed.ogg", 1.0),
+            ("assets/sounds/portal.ogg", 1.0),
+            ("assets/sounds/bounce.ogg", 1.0),
+            ("assets/sounds/depth_ball_attack.ogg", 1.0),
+            ("assets/sounds/depth_ball_birth_death.ogg", 1.0),
+            ("assets/sounds/eraser.wav", 1.0),
+            ("assets/sounds/attracted.ogg", 1.0),
+            ("assets/sounds/depth_ball_attack.ogg", 0.7),
+            ("assets/sounds/depth_ball_attack.ogg", 0.5),
+            ("assets/sounds/depth_ball_attack.ogg", 1.5),
+            ("assets/sounds/depth_ball_attack.ogg", 2.0),
+            ("assets/sounds/kill.ogg", 1.5),
+            ("assets/sounds/portal.ogg", 0.6),
         ];
 
         let mut sound_files = if cfg!(feature = "packed") {
@@ -295,10 +335,16 @@ lazy_static! {
                 Cursor::new(include_bytes!("../assets/sounds/depth_ball_birth_death.ogg").iter().cloned().collect::<Vec<_>>()),
                 Cursor::new(include_bytes!("../assets/sounds/eraser.wav").iter().cloned().collect::<Vec<_>>()),
                 Cursor::new(include_bytes!("../assets/sounds/attracted.ogg").iter().cloned().collect::<Vec<_>>()),
+                Cursor::new(include_bytes!("../assets/sounds/depth_ball_attack.ogg").iter().cloned().collect::<Vec<_>>()),
+                Cursor::new(include_bytes!("../assets/sounds/depth_ball_attack.ogg").iter().cloned().collect::<Vec<_>>()),
+                Cursor::new(include_bytes!("../assets/sounds/depth_ball_attack.ogg").iter().cloned().collect::<Vec<_>>()),
+                Cursor::new(include_bytes!("../assets/sounds/depth_ball_attack.ogg").iter().cloned().collect::<Vec<_>>()),
+                Cursor::new(include_bytes!("../assets/sounds/kill.ogg").iter().cloned().collect::<Vec<_>>()),
+                Cursor::new(include_bytes!("../assets/sounds/portal.ogg").iter().cloned().collect::<Vec<_>>()),
             ]
         } else {
             sound_filenames.iter()
-                .map(|s| {
+                .map(|&(s, _)| {
                     let mut buffer = vec![];
                     let mut file = File::open(s)
                         .unwrap_or_else_show(|e| format!("Failed to open sound {}: {}", s, e));
@@ -310,11 +356,11 @@ lazy_static! {
         };
 
         let mut sound_buffers = vec![];
-        for (file, filename) in sound_files.drain(..).zip(sound_filenames.iter()) {
+        for (file, &(filename, pitch)) in sound_files.drain(..).zip(sound_filenames.iter()) {
             let sound = Decoder::new(file)
                 .unwrap_or_else_show(|e| format!("Failed to decode sound {}: {}", filename, e));
 
-            let sound = SoundBuffer::new(sound)
+            let sound = SoundBuffer::new(sound, pitch)
                 .unwrap_or_else_show(|e| format!("Invalid sound: {}: {}", filename, e));
 
             sound_buffers.push(sound);
@@ -334,11 +380,7 @@ impl Audio {
     pub fn init(save: &::resource::Save) -> Self {
         let endpoint = ::rodio::default_output_device();
         if endpoint.is_none() {
-            return Audio {
-                audio_sink_control: None,
-                music_sink: None,
-                _audio_sink: None,
-            };
+            return Audio::mute();
         }
         let endpoint = endpoint.unwrap();
 
@@ -357,11 +399,11 @@ impl Audio {
                     audio_mix.set_listener(control.left_ear, control.right_ear);
 
                     for (sound, position) in control.spatial_sounds_to_add.drain(..) {
-                        audio_mix.add_spatial(SOUND_BUFFERS[sound as usize].source(), position);
+                        audio_mix.add_spatial(SOUND_BUFFERS[sound as usize].source(control.speed), position);
                     }
 
                     for sound in control.sounds_to_add.drain(..) {
-                        audio_mix.add_unspatial(SOUND_BUFFERS[sound as usize].source());
+                        audio_mix.add_unspatial(SOUND_BUFFERS[sound as usize].source(control.speed));
                     }
                 }
             );
@@ -397,6 +439,15 @@ impl Audio {
         }
     }
 
+    /// Audio without output device, sounds are ignored
+    pub fn mute() -> Self {
+        Audio {
+            audio_sink_control: None,
+            music_sink: None,
+            _audio_sink: None,
+        }
+    }
+
     pub fn play_unspatial(&self, sound: Sound) {
         if let Some(ref control) = self.audio_sink_control {
             let mut control = control.lock().unwrap();
@@ -411,7 +462,8 @@ impl Audio {
         }
     }
 
-    pub fn update(&mut self, position: ::na::Vector3<f32>, aim: ::na::UnitQuaternion<f32>, effect_volume: f32, music_volume: f32, eraser_volume: f32) {
+    /// Speed is applied to the sounds played afterward
+    pub fn update(&mut self, position: ::na::Vector3<f32>, aim: ::na::UnitQuaternion<f32>, effect_volume: f32, music_volume: f32, eraser_volume: f32, speed: f32) {
         if let Some(ref control) = self.audio_sink_control {
             let local_left_ear = ::na::Point3::new(0.0, - ::CONFIG.ear_distance/2.0, 0.0);
             let local_right_ear = ::na::Point3::new(0.0, ::CONFIG.ear_distance/2.0, 0.0);
@@ -426,6 +478,7 @@ impl Audio {
 
             let mut control = control.lock().unwrap();
             control.effect_volume = effect_volume;
+            control.speed = speed;
             control.left_ear = left_ear.coords.into();
             control.right_ear = right_ear.coords.into();
             if eraser_volume != control.eraser_volume {
@@ -33,27 +33,103 @@ impl ::specs::Component for Life {
     type Storage = ::specs::VecStorage<Self>;
 }
 
-pub struct Shooter {
+/// How bullets come back in the weapon
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ReloadMode {
+    /// Bullets are reloaded one by one
+    PerBullet,
+    /// All bullets are reloaded at once when the weapon is empty
+    Magazine,
+}
+
+fn one() -> usize {
+    1
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Weapon {
+    /// Kill all lifes on the ray until a wall, otherwise only the first one
+    pub piercing: bool,
+    pub bullets: usize,
+    pub reload_mode: ReloadMode,
     pub reload_time: f32,
+    /// Number of rays fired by a bullet
+    #[serde(default = "one")]
+    pub rays: usize,
+    /// Horizontal angle covered by the rays
+    #[serde(default)]
+    pub spread_angle: f32,
+    /// Number of bullets fired by a trigger
+    #[serde(default = "one")]
+    pub burst: usize,
+    #[serde(default)]
+    pub burst_interval: f32,
+    /// Time the trigger must be held for the release to fire, the charged shot
+    /// pierces like a piercing weapon
+    #[serde(default)]
+    pub charge_time: Option<f32>,
+    /// Number of bullets that can be reloaded during the level
+    #[serde(default)]
+    pub ammo: Option<usize>,
+}
+
+impl Weapon {
+    pub fn bullet_color(&self) -> ::graphics::Color {
+        if self.piercing {
+            ::CONFIG.weapon_bullet_color
+        } else {
+            ::CONFIG.weapon_first_hit_bullet_color
+        }
+    }
+}
+
+pub struct Shooter {
+    pub weapon: Weapon,
     pub timer: f32,
-    pub max_bullets: usize,
     pub bullets: usize,
+    pub ammo: Option<usize>,
     pub shoot: bool,
+    pub trigger: bool,
+    pub charge: f32,
+    pub burst: usize,
+    pub burst_timer: f32,
 }
 
 impl Shooter {
-    pub fn new(reload_time: f32, max_bullets: usize) -> Self {
+    pub fn new(weapon: Weapon) -> Self {
         Shooter {
-            reload_time,
-            shoot: false,
-            max_bullets,
+            bullets: weapon.bullets,
+            ammo: weapon.ammo,
+            weapon,
             timer: 0.0,
-            bullets: max_bullets,
+            shoot: false,
+            trigger: false,
+            charge: 0.0,
+            burst: 0,
+            burst_timer: 0.0,
+        }
+    }
+
+    /// Shoot on release if charged
+    pub fn set_trigger(&mut self, pressed: bool) {
+        self.shoot = !pressed && self.charged();
+        if pressed {
+            self.charge = 0.0;
         }
+        self.trigger = pressed;
+    }
+
+    pub fn charged(&self) -> bool {
+        self.weapon.charge_time.map(|t| self.charge >= t).unwrap_or(true)
+    }
+
+    /// Whether the shot kills all lifes on the ray
+    pub fn piercing(&self) -> bool {
+        self.weapon.piercing || (self.weapon.charge_time.is_some() && self.charged())
     }
 
-    pub fn set_shoot(&mut self, shoot: bool) {
-        self.shoot = shoot;
+    pub fn can_reload(&self) -> bool {
+        self.ammo != Some(0)
     }
 }
 
@@ -463,6 +539,12 @@ pub struct Turret {
     pub last_shoot: f32,
     pub reload_time: f32,
     pub position: ::na::Vector3<f32>,
+    /// Do not shoot if player is further
+    pub range: Option<f32>,
+    pub projectile_velocity: f32,
+    /// 0.0 shoot at player position, 1.0 shoot where the player will be
+    pub lead_factor: f32,
+    pub effect: ProjectileEffect,
 }
 
 impl ::specs::Component for Turret {
@@ -470,21 +552,76 @@ impl ::specs::Component for Turret {
 }
 
 impl Turret {
-    pub fn new(reload_time: f32, position: ::na::Vector3<f32>) -> Self {
+    pub fn new(
+        reload_time: f32,
+        position: ::na::Vector3<f32>,
+        range: Option<f32>,
+        projectile_velocity: f32,
+        lead_factor: f32,
+        effect: ProjectileEffect,
+    ) -> Self {
         Turret {
             reload_time,
             position,
+            range,
+            projectile_velocity,
+            lead_factor,
+            effect,
             last_shoot: 0.0,
         }
     }
 }
 
-/// Die on contact and decrease depth if contact with player
-#[derive(Default)]
-pub struct DepthBall;
+/// Effect applied to the player when hit by a depth ball
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ProjectileEffect {
+    /// Divide depth coefficient
+    Depth,
+    /// Reduce player velocity
+    Slow,
+    /// Reduce view distance
+    Blind,
+    /// Invert player directions
+    Invert,
+    /// Push player in the direction of the ball
+    Push,
+}
+
+impl Default for ProjectileEffect {
+    fn default() -> Self {
+        ProjectileEffect::Depth
+    }
+}
+
+impl ProjectileEffect {
+    pub fn color(&self) -> ::graphics::Color {
+        match *self {
+            ProjectileEffect::Depth => ::CONFIG.depth_ball_color,
+            ProjectileEffect::Slow => ::CONFIG.slow_ball_color,
+            ProjectileEffect::Blind => ::CONFIG.blind_ball_color,
+            ProjectileEffect::Invert => ::CONFIG.invert_ball_color,
+            ProjectileEffect::Push => ::CONFIG.push_ball_color,
+        }
+    }
+
+    pub fn attack_sound(&self) -> ::audio::Sound {
+        match *self {
+            ProjectileEffect::Depth => ::audio::Sound::DepthBallAttack,
+            ProjectileEffect::Slow => ::audio::Sound::SlowBallAttack,
+            ProjectileEffect::Blind => ::audio::Sound::BlindBallAttack,
+            ProjectileEffect::Invert => ::audio::Sound::InvertBallAttack,
+            ProjectileEffect::Push => ::audio::Sound::PushBallAttack,
+        }
+    }
+}
+
+/// Die on contact and apply its effect if contact with player
+pub struct DepthBall {
+    pub effect: ProjectileEffect,
+}
 
 impl ::specs::Component for DepthBall {
-    type Storage = ::specs::NullStorage<Self>;
+    type Storage = ::specs::VecStorage<Self>;
 }
 
 #[allow(unused)]
@@ -511,25 +648,156 @@ impl ::specs::Component for Teleport {
     type Storage = ::specs::VecStorage<Self>;
 }
 
-#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Copy)]
-pub enum GeneratedEntity {
-    Avoider,
-    Bouncer,
-}
-
 pub struct Generator {
     pub pos: ::na::Vector3<f32>,
-    pub entity: GeneratedEntity,
+    pub entity: ::entity::EntityConf,
     pub salvo: usize,
     pub timer: f32,
     pub time_between_salvo: f32,
     pub eraser_probability: f32,
+    /// Maximum number of generated entities alive at the same time
+    pub max_alive: Option<usize>,
+    /// Total number of entities the generator can spawn
+    pub budget: Option<usize>,
+    pub spawned: usize,
+    /// Whereas the generator has a body that can be shot
+    pub has_body: bool,
+    /// Whereas generated entities flock together
+    pub flocking: bool,
 }
 
 impl ::specs::Component for Generator {
     type Storage = ::specs::VecStorage<Self>;
 }
 
+impl Generator {
+    pub fn budget_exhausted(&self) -> bool {
+        self.budget.map(|budget| self.spawned >= budget).unwrap_or(false)
+    }
+
+    /// A generator without body nor budget spawns forever so it doesn't prevent completion
+    pub fn prevent_completion(&self) -> bool {
+        self.has_body || (self.budget.is_some() && !self.budget_exhausted())
+    }
+}
+
+/// Entity spawned by a generator
+pub struct Generated {
+    pub generator: ::specs::Entity,
+}
+
+impl ::specs::Component for Generated {
+    type Storage = ::specs::VecStorage<Self>;
+}
+
+/// Effect given to the player when a pickup is collected
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum PickupEffect {
+    /// Fill all bullets
+    Reload,
+    /// Add one bullet to the weapon
+    ExtraBullet,
+    SpeedBoost,
+    SlowMotion,
+    Invulnerability,
+    /// Unlock the doors with the same key
+    Key(usize),
+}
+
+impl PickupEffect {
+    pub fn color(&self) -> ::graphics::Color {
+        match *self {
+            PickupEffect::Reload => ::CONFIG.pickup_reload_color,
+            PickupEffect::ExtraBullet => ::CONFIG.pickup_extra_bullet_color,
+            PickupEffect::SpeedBoost => ::CONFIG.pickup_speed_boost_color,
+            PickupEffect::SlowMotion => ::CONFIG.pickup_slow_motion_color,
+            PickupEffect::Invulnerability => ::CONFIG.pickup_invulnerability_color,
+            PickupEffect::Key(key) => ::CONFIG.door_key_colors[key % ::CONFIG.door_key_colors.len()],
+        }
+    }
+}
+
+pub struct Pickup {
+    pub effect: PickupEffect,
+}
+
+impl ::specs::Component for Pickup {
+    type Storage = ::specs::VecStorage<Self>;
+}
+
+/// How a door of a level is opened
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum DoorKind {
+    /// Shoot the switch placed before the door
+    Switch,
+    /// Kill all monsters placed before the door
+    Clear,
+    /// Pick up the key placed before the door
+    Key,
+}
+
+pub enum DoorLock {
+    Switch(::specs::Entity),
+    Clear(Vec<::specs::Entity>),
+    Key(usize),
+}
+
+impl DoorLock {
+    pub fn color(&self) -> ::graphics::Color {
+        match *self {
+            DoorLock::Switch(_) => ::CONFIG.door_switch_color,
+            DoorLock::Clear(_) => ::CONFIG.door_clear_color,
+            DoorLock::Key(key) => PickupEffect::Key(key).color(),
+        }
+    }
+}
+
+pub struct Door {
+    pub lock: DoorLock,
+}
+
+impl ::specs::Component for Door {
+    type Storage = ::specs::VecStorage<Self>;
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum HazardKind {
+    /// Always killing floor in rooms
+    Spikes,
+    /// Killing gate across a corridor that toggles on a period
+    LaserGate,
+}
+
+/// Kill the player on contact while active
+pub struct Hazard {
+    /// Active and inactive durations, always active if none
+    pub period: Option<(f32, f32)>,
+    pub timer: f32,
+    pub active: bool,
+}
+
+impl ::specs::Component for Hazard {
+    type Storage = ::specs::VecStorage<Self>;
+}
+
+impl Hazard {
+    pub fn new(period: Option<(f32, f32)>, timer: f32) -> Self {
+        Hazard {
+            period,
+            timer,
+            active: true,
+        }
+    }
+}
+
+/// Player of the best run of the level
+#[derive(Default)]
+pub struct Ghost;
+
+impl ::specs::Component for Ghost {
+    type Storage = ::specs::NullStorage<Self>;
+}
+
 pub struct Anchor {
     pub entity: ::specs::Entity,
     pub local_pos: ::na::Point3<f32>,
@@ -537,9 +805,23 @@ pub struct Anchor {
     pub pos: ::na::Vector3<f32>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum HookMode {
+    /// Constant force toward the anchor
+    Pull,
+    /// Maximum distance to the anchor, the player swings
+    Rope,
+}
+
 pub struct Hook {
+    pub mode: HookMode,
     pub launch: bool,
     pub force: f32,
+    pub range: f32,
+    pub reel_in: bool,
+    pub reel_out: bool,
+    /// Current length of the rope, set when anchored in rope mode
+    pub length: Option<f32>,
     pub anchor: Option<Anchor>,
     pub draw: ::specs::Entity,
 }
@@ -549,16 +831,20 @@ impl ::specs::Component for Hook {
 }
 
 impl Hook {
-    pub fn new(force: f32, draw: ::specs::Entity) -> Self {
+    pub fn new(mode: HookMode, force: f32, range: f32, draw: ::specs::Entity) -> Self {
         Hook {
+            mode,
             launch: false,
             force,
+            range,
+            reel_in: false,
+            reel_out: false,
+            length: None,
             anchor: None,
             draw,
         }
     }
 
-#[allow(unused)]
     pub fn set_launch(&mut self, launch: bool) {
         self.launch = launch;
     }
@@ -592,6 +878,10 @@ impl Reducer {
 
 pub struct Attracted {
     pub last_update: f32,
+    /// Position the player was last seen at and time left to reach it
+    pub last_seen: Option<(::na::Vector3<f32>, f32)>,
+    /// Time left searching around the last seen position
+    pub search: f32,
 }
 
 impl ::specs::Component for Attracted {
@@ -602,10 +892,121 @@ impl Attracted {
     pub fn new() -> Self {
         Attracted {
             last_update: 0.0,
+            last_seen: None,
+            search: 0.0,
         }
     }
 }
 
+/// State of an enemy controlled by an AI
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum AiState {
+    /// Don't move
+    Idle,
+    /// Wander in random directions
+    Patrol,
+    /// Go to the position the player was last seen at
+    Alert,
+    /// Go to the player
+    Chase,
+    /// Go away from the player
+    Flee,
+    /// Don't move, usually for a short time after being damaged
+    Stunned,
+}
+
+/// Distances are in cm and durations in ms so configurations can be hashed
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AiCondition {
+    Seen,
+    Unseen,
+    /// The player is closer than the distance
+    Closer(usize),
+    /// The player is further than the distance
+    Further(usize),
+    /// The current state has lasted the duration
+    After(usize),
+    /// Shot without being killed, only eraser enemies survive a shot
+    Damaged,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct AiTransition {
+    pub from: AiState,
+    pub to: AiState,
+    pub condition: AiCondition,
+}
+
+/// Transitions are checked in order, the first one that matches is taken
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct AiConf {
+    pub initial: AiState,
+    pub transitions: Vec<AiTransition>,
+}
+
+/// Replace the control of the enemy kind by a state machine
+pub struct Ai {
+    pub conf: AiConf,
+    pub state: AiState,
+    /// Time spent in the current state
+    pub timer: f32,
+    pub last_update: f32,
+    pub last_seen: Option<::na::Vector3<f32>>,
+    pub damaged: bool,
+    pub patrol_direction: ::na::Vector3<f32>,
+    pub patrol_timer: f32,
+}
+
+impl ::specs::Component for Ai {
+    type Storage = ::specs::VecStorage<Self>;
+}
+
+impl Ai {
+    pub fn new(conf: AiConf) -> Self {
+        Ai {
+            state: conf.initial,
+            conf,
+            timer: 0.0,
+            last_update: 0.0,
+            last_seen: None,
+            damaged: false,
+            patrol_direction: ::na::zero(),
+            patrol_timer: 0.0,
+        }
+    }
+
+    /// Take the first transition whose condition holds
+    pub fn transition(&mut self, seen: bool, distance: f32) {
+        let next = self.conf.transitions
+            .iter()
+            .filter(|transition| transition.from == self.state)
+            .find(|transition| match transition.condition {
+                AiCondition::Seen => seen,
+                AiCondition::Unseen => !seen,
+                AiCondition::Closer(cm) => distance < cm as f32 / 100.0,
+                AiCondition::Further(cm) => distance > cm as f32 / 100.0,
+                AiCondition::After(ms) => self.timer >= ms as f32 / 1000.0,
+                AiCondition::Damaged => self.damaged,
+            })
+            .map(|transition| transition.to);
+
+        self.damaged = false;
+        if let Some(next) = next {
+            self.state = next;
+            self.timer = 0.0;
+        }
+    }
+}
+
+/// Steer with separation, alignment and cohesion among entities of the same group
+pub struct Flocking {
+    pub group: usize,
+}
+
+impl ::specs::Component for Flocking {
+    type Storage = ::specs::VecStorage<Self>;
+}
+
 #[derive(Default)]
 pub struct Motionless;
 
@@ -619,3 +1020,42 @@ pub struct Activated;
 impl ::specs::Component for Activated {
     type Storage = ::specs::NullStorage<Self>;
 }
+
+/// Walk along route and chase the player while it is in sight
+pub struct Patroller {
+    pub route: Vec<::na::Vector3<f32>>,
+    pub next: usize,
+    pub goal: Option<::na::Vector3<f32>>,
+    pub chasing: bool,
+    pub last_update: f32,
+}
+
+impl ::specs::Component for Patroller {
+    type Storage = ::specs::VecStorage<Self>;
+}
+
+impl Patroller {
+    pub fn new(route: Vec<::na::Vector3<f32>>) -> Self {
+        assert!(!route.is_empty());
+        Patroller {
+            route,
+            next: 0,
+            goal: None,
+            chasing: false,
+            last_update: 0.0,
+        }
+    }
+
+    /// Set next to the nearest waypoint of the route
+    pub fn rejoin_route(&mut self, pos: ::na::Vector3<f32>) {
+        self.next = self.route
+            .iter()
+            .enumerate()
+            .min_by(|a, b| {
+                (a.1 - pos).norm().partial_cmp(&(b.1 - pos).norm()).unwrap()
+            })
+            .unwrap()
+            .0;
+        self.goal = None;
+    }
+}
@@ -22,6 +22,18 @@ lazy_static! {
         let mut config: Config = ::ron::de::from_reader(file)
             .unwrap_or_else_show(|e| format!("Failed to parse config file {}: {}", FILENAME, e));
 
+        for (i, level) in config.levels.iter().enumerate() {
+            if let Some(ref medals) = level.medals {
+                if !(medals.gold <= medals.silver && medals.silver <= medals.bronze) {
+                    ::show_message::show(format!(
+                        "Invalid config file {}: medal times of level {} must be gold <= silver <= bronze",
+                        FILENAME,
+                        i + 1,
+                    ));
+                }
+            }
+        }
+
         if let Ok(Ok(val)) = ::std::env::var("HYPERZEN_TRAINING_SHOW_WEAPON").map(|val| val.parse::<bool>()) {
             config.player_show_weapon = val;
         }
@@ -43,6 +55,7 @@ pub struct Config {
     pub activated_color: ::graphics::Color,
 
     pub depth_coef_divider: f32,
+    /// Growth of the depth coefficient per second
     pub depth_coef_velocity: f32,
     pub depth_coef_min: f32,
     pub ear_distance: f32,
@@ -65,8 +78,16 @@ pub struct Config {
     pub mouse_sensibility: f32,
     pub fps: u32,
     pub eraser_time: f32,
-    pub physic_max_step_time: f32,
-    pub physic_min_step_time: f32,
+    /// Fixed duration of a game update, the physic world is stepped once per update
+    pub update_step_time: f32,
+    /// Maximum number of updates per frame, the game slows down beyond
+    pub max_update_steps: usize,
+    /// Number of attempts kept in the statistics of the save
+    pub stats_history: usize,
+    /// Duration of the achievement unlocked notification in seconds
+    pub achievement_notification_duration: f32,
+    /// Duration of the notification of a run rejected by the verification in seconds
+    pub run_rejected_notification_duration: f32,
 
     pub accumulated_impulse_solver_step: f32,
     pub correction_mode_a: f32,
@@ -84,6 +105,15 @@ pub struct Config {
     pub avoider_color: ::graphics::Color,
     pub avoider_avoid_norm: f32,
 
+    pub ai_update_time: f32,
+    pub ai_patrol_turn_time: f32,
+
+    pub flocking_radius: f32,
+    pub flocking_separation_radius: f32,
+    pub flocking_separation_weight: f32,
+    pub flocking_alignment_weight: f32,
+    pub flocking_cohesion_weight: f32,
+
     pub bouncer_size: f32,
     pub bouncer_velocity: f32,
     pub bouncer_time_to_reach_vmax: f32,
@@ -99,6 +129,47 @@ pub struct Config {
     pub depth_ball_time_to_reach_vmax: f32,
     pub depth_ball_ang_damping: f32,
     pub depth_ball_color: ::graphics::Color,
+    pub slow_ball_color: ::graphics::Color,
+    pub blind_ball_color: ::graphics::Color,
+    pub invert_ball_color: ::graphics::Color,
+    pub push_ball_color: ::graphics::Color,
+
+    pub slow_effect_duration: f32,
+    pub slow_effect_coef: f32,
+    pub blind_effect_duration: f32,
+    pub blind_effect_far_plane: f32,
+    pub invert_effect_duration: f32,
+    pub push_effect_velocity: f32,
+
+    pub pickup_size: f32,
+    pub pickup_reload_color: ::graphics::Color,
+    pub pickup_extra_bullet_color: ::graphics::Color,
+    pub pickup_speed_boost_color: ::graphics::Color,
+    pub pickup_slow_motion_color: ::graphics::Color,
+    pub pickup_invulnerability_color: ::graphics::Color,
+    pub pickup_speed_boost_duration: f32,
+    pub pickup_speed_boost_coef: f32,
+    pub pickup_slow_motion_duration: f32,
+    pub pickup_slow_motion_coef: f32,
+    pub pickup_invulnerability_duration: f32,
+
+    pub door_switch_color: ::graphics::Color,
+    pub door_clear_color: ::graphics::Color,
+    /// Color of key doors and their keys, indexed by key modulo length
+    pub door_key_colors: Vec<::graphics::Color>,
+    pub door_switch_size: f32,
+
+    pub hazard_spikes_color: ::graphics::Color,
+    pub hazard_spikes_height: f32,
+    pub hazard_laser_gate_color: ::graphics::Color,
+    pub hazard_laser_gate_width: f32,
+    pub hazard_laser_gate_active_time: f32,
+    pub hazard_laser_gate_inactive_time: f32,
+
+    pub ghost_color: ::graphics::Color,
+
+    pub hit_stop_duration: f32,
+    pub hit_stop_scale: f32,
 
     pub attracted_size: f32,
     pub attracted_velocity: f32,
@@ -106,6 +177,23 @@ pub struct Config {
     pub attracted_ang_damping: f32,
     pub attracted_color: ::graphics::Color,
     pub attracted_update_time: f32,
+    /// Time spent going to the last seen position before giving up
+    pub attracted_memory_duration: f32,
+    pub attracted_last_seen_reach_distance: f32,
+    /// Time spent wandering once the last seen position is reached
+    pub attracted_search_duration: f32,
+
+    pub patroller_size: f32,
+    pub patroller_velocity: f32,
+    pub patroller_time_to_reach_vmax: f32,
+    pub patroller_ang_damping: f32,
+    pub patroller_color: ::graphics::Color,
+    pub patroller_update_time: f32,
+    pub patroller_waypoint_distance: f32,
+
+    pub generator_size: f32,
+    pub generator_density: f32,
+    pub generator_color: ::graphics::Color,
 
     pub avoider_generator_salvo: usize,
     pub avoider_generator_eraser_probability: f32,
@@ -122,7 +210,20 @@ pub struct Config {
     pub player_hook_time_to_reach_vmax: f32,
     pub player_ang_damping: f32,
     pub player_gravity: f32,
+    /// Roll in zero gravity in radian per second
+    pub player_roll_velocity: f32,
+    /// Velocity multiplier while dashing
+    pub player_dash_coef: f32,
+    pub player_dash_duration: f32,
+    pub player_dash_cooldown: f32,
+    /// Rooms are 1.0 high, the jump must stay under the ceiling: velocity²/(2*gravity) < 0.8
+    pub player_jump_velocity: f32,
+    /// Gravity acceleration on levels where the player can jump
+    pub player_jump_gravity: f32,
     pub player_hook_force: f32,
+    pub player_hook_range: f32,
+    pub player_hook_min_length: f32,
+    pub player_hook_reel_velocity: f32,
     pub player_hook_color: ::graphics::Color,
     pub player_hook_size: f32,
     pub player_show_weapon: bool,
@@ -140,22 +241,26 @@ pub struct Config {
     pub turret_color: ::graphics::Color,
     pub turret_density: f32,
     pub turret_reload_time: f32,
+    pub turret_lead_factor: f32,
 
     pub wall_color: Vec<::graphics::Color>,
 
-    pub weapon_reload_time: f32,
-    pub weapon_bullet_nbr: usize,
+    pub weapon: ::component::Weapon,
     pub weapon_bullet_radius: f32,
     pub weapon_bullet_length: f32,
     pub weapon_bullet_x: f32,
     pub weapon_bullet_dx: f32,
     pub weapon_bullet_color: ::graphics::Color,
+    pub weapon_first_hit_bullet_color: ::graphics::Color,
+    pub weapon_charged_bullet_color: ::graphics::Color,
     pub weapon_bullet_empty_color: ::graphics::Color,
     pub weapon_six_color: ::graphics::Color,
     pub weapon_angle_color: ::graphics::Color,
     pub weapon_light_ray_duration: f32,
 
-    pub levels: Vec<Vec<::level::Level>>,
+    pub levels: Vec<::level::LevelConf>,
+    /// Medal required on a level to open the next one in the hall, completing it is enough if none
+    pub hall_unlock_medal: Option<::level::Medal>,
 }
 
 impl Config {
@@ -1,6 +1,6 @@
 use rand::distributions::{IndependentSample, Range};
 
-pub fn create_attracted_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::specs::World) {
+pub fn create_attracted_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::specs::World) -> ::specs::Entity {
     create_attracted(
         pos,
         eraser,
@@ -14,7 +14,7 @@ pub fn create_attracted_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::specs
         &mut world.write(),
         &mut world.write_resource(),
         &world.read_resource(),
-    );
+    )
 }
 
 pub fn create_attracted<'a>(
@@ -30,7 +30,7 @@ pub fn create_attracted<'a>(
     contactors: &mut ::specs::WriteStorage<'a, ::component::Contactor>,
     physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
     entities: &::specs::Entities,
-) {
+) -> ::specs::Entity {
     let primitive_trans = ::graphics::resizer(
         ::CONFIG.attracted_size,
         ::CONFIG.attracted_size,
@@ -40,13 +40,15 @@ pub fn create_attracted<'a>(
     let shape = ::ncollide::shape::Ball3::new(::CONFIG.attracted_size);
     let mut pos = ::na::Isometry3::new(pos, ::na::zero());
 
-    let mut rng = ::rand::thread_rng();
     let range = Range::new(-0.5 + ::CONFIG.attracted_size, 0.5 - ::CONFIG.attracted_size);
-    pos.translation.vector += ::na::Vector3::new(
-        range.ind_sample(&mut rng),
-        range.ind_sample(&mut rng),
-        range.ind_sample(&mut rng),
-    );
+    pos.translation.vector += {
+        let mut rng = ::util::rng();
+        ::na::Vector3::new(
+            range.ind_sample(&mut *rng),
+            range.ind_sample(&mut *rng),
+            range.ind_sample(&mut *rng),
+        )
+    };
 
     let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
     group.set_membership(&[super::ALIVE_GROUP, super::MONSTER_GROUP, super::KILLER_GROUP]);
@@ -71,7 +73,7 @@ pub fn create_attracted<'a>(
             ::na::zero(),
             None,
         );
-        momentum.direction = ::na::Vector3::new_random().normalize();
+        momentum.direction = ::util::random_vector3().normalize();
         momentum
     });
     contactors.insert(entity, ::component::Contactor::new());
@@ -93,4 +95,6 @@ pub fn create_attracted<'a>(
     }
 
     ::component::PhysicBody::add(entity, body, bodies, physic_world);
+
+    entity
 }
@@ -1,4 +1,4 @@
-pub fn create_avoider_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::specs::World) {
+pub fn create_avoider_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::specs::World) -> ::specs::Entity {
     create_avoider(
         pos,
         eraser,
@@ -27,7 +27,7 @@ pub fn create_avoider<'a>(
     lifes: &mut ::specs::WriteStorage<'a, ::component::Life>,
     physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
     entities: &::specs::Entities,
-) {
+) -> ::specs::Entity {
     let primitive_trans = ::graphics::resizer(
         ::CONFIG.avoider_size,
         ::CONFIG.avoider_size,
@@ -94,4 +94,6 @@ pub fn create_avoider<'a>(
     }
 
     ::component::PhysicBody::add(entity, body, bodies, physic_world);
+
+    entity
 }
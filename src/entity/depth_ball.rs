@@ -1,8 +1,10 @@
 #[allow(unused)]
-pub fn create_depth_ball_w(pos: ::na::Vector3<f32>, dir: ::na::Vector3<f32>, world: &::specs::World) {
+pub fn create_depth_ball_w(pos: ::na::Vector3<f32>, dir: ::na::Vector3<f32>, velocity: f32, effect: ::component::ProjectileEffect, world: &::specs::World) {
     create_depth_ball(
         pos,
         dir,
+        velocity,
+        effect,
         &mut world.write(),
         &mut world.write(),
         &mut world.write(),
@@ -18,6 +20,8 @@ pub fn create_depth_ball_w(pos: ::na::Vector3<f32>, dir: ::na::Vector3<f32>, wor
 pub fn create_depth_ball<'a>(
     pos: ::na::Vector3<f32>,
     dir: ::na::Vector3<f32>,
+    velocity: f32,
+    effect: ::component::ProjectileEffect,
     momentums: &mut ::specs::WriteStorage<'a, ::component::Momentum>,
     depth_balls: &mut ::specs::WriteStorage<'a, ::component::DepthBall>,
     contactors: &mut ::specs::WriteStorage<'a, ::component::Contactor>,
@@ -50,11 +54,11 @@ pub fn create_depth_ball<'a>(
     let (primitive, groups) = ::graphics::Primitive::Sphere.instantiate();
 
     let entity = entities.create();
-    depth_balls.insert(entity, ::component::DepthBall);
+    depth_balls.insert(entity, ::component::DepthBall { effect });
     momentums.insert(entity, {
         let mut momentum = ::component::Momentum::new(
             mass,
-            ::CONFIG.depth_ball_velocity,
+            velocity,
             ::CONFIG.depth_ball_time_to_reach_vmax,
             None,
             ::CONFIG.depth_ball_ang_damping,
@@ -70,7 +74,7 @@ pub fn create_depth_ball<'a>(
         ::component::DynamicGraphicsAssets::new(
             primitive,
             groups,
-            ::CONFIG.depth_ball_color,
+            effect.color(),
             primitive_trans,
         ),
     );
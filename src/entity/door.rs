@@ -0,0 +1,116 @@
+pub fn create_door_w(
+    pos: ::na::Vector3<f32>,
+    radius: f32,
+    lock: ::component::DoorLock,
+    world: &::specs::World,
+) -> ::specs::Entity {
+    create_door(
+        pos,
+        radius,
+        lock,
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write_resource(),
+        &world.read_resource(),
+    )
+}
+
+/// A cube filling a maze cell that blocks like a wall until its lock is opened
+pub fn create_door<'a>(
+    pos: ::na::Vector3<f32>,
+    radius: f32,
+    lock: ::component::DoorLock,
+    doors: &mut ::specs::WriteStorage<'a, ::component::Door>,
+    bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
+    dynamic_draws: &mut ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+    entities: &::specs::Entities,
+) -> ::specs::Entity {
+    let primitive_trans = ::graphics::resizer(radius, radius, radius);
+
+    let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_static();
+    group.set_membership(&[super::WALL_GROUP]);
+
+    let shape = ::ncollide::shape::Cuboid::new(::na::Vector3::new(radius, radius, radius));
+    let mut body = ::nphysics::object::RigidBody::new_static(shape, 10.0, 10.0);
+    body.set_collision_groups(group);
+    body.set_transformation(::na::Isometry3::new(pos, ::na::zero()));
+
+    let (primitive, groups) = ::graphics::Primitive::Cube.instantiate();
+
+    let entity = entities.create();
+    dynamic_graphics_assets.insert(
+        entity,
+        ::component::DynamicGraphicsAssets::new(
+            primitive,
+            groups,
+            lock.color(),
+            primitive_trans,
+        ),
+    );
+    dynamic_draws.insert(entity, ::component::DynamicDraw);
+    doors.insert(entity, ::component::Door { lock });
+
+    ::component::PhysicBody::add(entity, body, bodies, physic_world);
+
+    entity
+}
+
+pub fn create_door_switch_w(pos: ::na::Vector3<f32>, world: &::specs::World) -> ::specs::Entity {
+    create_door_switch(
+        pos,
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write_resource(),
+        &world.read_resource(),
+    )
+}
+
+/// A static target that opens its door when shot
+pub fn create_door_switch<'a>(
+    pos: ::na::Vector3<f32>,
+    bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
+    dynamic_draws: &mut ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    lifes: &mut ::specs::WriteStorage<'a, ::component::Life>,
+    physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+    entities: &::specs::Entities,
+) -> ::specs::Entity {
+    let primitive_trans = ::graphics::resizer(
+        ::CONFIG.door_switch_size,
+        ::CONFIG.door_switch_size,
+        ::CONFIG.door_switch_size,
+    );
+
+    let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_static();
+    group.set_membership(&[super::ALIVE_GROUP, super::MONSTER_GROUP]);
+
+    let shape = ::ncollide::shape::Cuboid3::new(::na::Vector3::from_element(::CONFIG.door_switch_size));
+    let mut body = ::nphysics::object::RigidBody::new_static(shape, 0.0, 0.0);
+    body.set_collision_groups(group);
+    body.set_transformation(::na::Isometry3::new(pos, ::na::zero()));
+
+    let (primitive, groups) = ::graphics::Primitive::PitCube.instantiate();
+
+    let entity = entities.create();
+    dynamic_graphics_assets.insert(
+        entity,
+        ::component::DynamicGraphicsAssets::new(
+            primitive,
+            groups,
+            ::CONFIG.door_switch_color,
+            primitive_trans,
+        ),
+    );
+    dynamic_draws.insert(entity, ::component::DynamicDraw);
+    lifes.insert(entity, ::component::Life::DrawAlive);
+
+    ::component::PhysicBody::add(entity, body, bodies, physic_world);
+
+    entity
+}
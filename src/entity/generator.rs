@@ -1,12 +1,53 @@
+pub fn create_generator_w(
+    pos: ::na::Vector3<f32>,
+    generated_entity: ::entity::EntityConf,
+    salvo: usize,
+    time_between_salvo: f32,
+    eraser_probability: f32,
+    max_alive: Option<usize>,
+    budget: Option<usize>,
+    body: bool,
+    flocking: bool,
+    world: &::specs::World,
+) -> ::specs::Entity {
+    create_generator(
+        pos,
+        generated_entity,
+        salvo,
+        time_between_salvo,
+        eraser_probability,
+        max_alive,
+        budget,
+        body,
+        flocking,
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write_resource(),
+        &world.read_resource(),
+    )
+}
+
 pub fn create_generator<'a>(
     pos: ::na::Vector3<f32>,
-    generated_entity: ::component::GeneratedEntity,
+    generated_entity: ::entity::EntityConf,
     salvo: usize,
     time_between_salvo: f32,
     eraser_probability: f32,
+    max_alive: Option<usize>,
+    budget: Option<usize>,
+    body: bool,
+    flocking: bool,
     generators: &mut ::specs::WriteStorage<'a, ::component::Generator>,
+    bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
+    dynamic_draws: &mut ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    lifes: &mut ::specs::WriteStorage<'a, ::component::Life>,
+    physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
     entities: &::specs::Entities,
-) {
+) -> ::specs::Entity {
     let entity = entities.create();
     generators.insert(
         entity,
@@ -17,6 +58,54 @@ pub fn create_generator<'a>(
             timer: 0.0,
             time_between_salvo,
             eraser_probability,
+            max_alive,
+            budget,
+            spawned: 0,
+            has_body: body,
+            flocking,
         },
     );
+
+    if body {
+        let primitive_trans = ::graphics::resizer(
+            ::CONFIG.generator_size,
+            ::CONFIG.generator_size,
+            ::CONFIG.generator_size,
+        );
+
+        let shape = ::ncollide::shape::Cuboid3::new(::na::Vector3::from_element(::CONFIG.generator_size));
+        let trans = ::na::Isometry3::new(pos, ::na::zero());
+
+        // Generated entities are created inside the generator body
+        let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
+        group.set_membership(&[super::ALIVE_GROUP, super::MONSTER_GROUP]);
+        group.set_blacklist(&[super::MONSTER_GROUP]);
+
+        let mut body = ::nphysics::object::RigidBody::new_dynamic(shape, ::CONFIG.generator_density, 0.0, 0.0);
+
+        body.set_transformation(trans);
+        body.set_collision_groups(group);
+
+        let (primitive, groups) = ::graphics::Primitive::PitCube.instantiate();
+
+        dynamic_graphics_assets.insert(
+            entity,
+            ::component::DynamicGraphicsAssets::new(
+                primitive,
+                groups,
+                ::CONFIG.generator_color,
+                primitive_trans,
+            ),
+        );
+        lifes.insert(entity, ::component::Life::DrawAlive);
+        dynamic_draws.insert(entity, ::component::DynamicDraw);
+
+        ::component::PhysicBody::add(entity, body, bodies, physic_world);
+        bodies
+            .get_mut(entity)
+            .unwrap()
+            .ball_in_socket(physic_world, ::na::Point3::from_coordinates(pos));
+    }
+
+    entity
 }
@@ -0,0 +1,34 @@
+pub fn create_ghost_w(world: &::specs::World) -> ::specs::Entity {
+    create_ghost(
+        &mut world.write(),
+        &mut world.write(),
+        &world.read_resource(),
+    )
+}
+
+/// Ghost of the best run, it is moved and drawn by the ghost system
+pub fn create_ghost<'a>(
+    ghosts: &mut ::specs::WriteStorage<'a, ::component::Ghost>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    entities: &::specs::Entities,
+) -> ::specs::Entity {
+    let (primitive, groups) = ::graphics::Primitive::Sphere.instantiate();
+    let primitive_trans = ::graphics::resizer(
+        ::CONFIG.player_radius,
+        ::CONFIG.player_radius,
+        ::CONFIG.player_height,
+    );
+
+    let entity = entities.create();
+    ghosts.insert(entity, ::component::Ghost);
+    dynamic_graphics_assets.insert(
+        entity,
+        ::component::DynamicGraphicsAssets::new(
+            primitive,
+            groups,
+            ::CONFIG.ghost_color,
+            primitive_trans,
+        ),
+    );
+    entity
+}
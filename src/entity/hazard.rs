@@ -0,0 +1,100 @@
+use rand::distributions::{IndependentSample, Range};
+
+pub fn create_spikes_w(pos: ::na::Vector3<f32>, radius: f32, world: &::specs::World) -> ::specs::Entity {
+    let half_extents = ::na::Vector3::new(radius * 0.8, radius * 0.8, radius);
+    let primitive_trans = ::na::Isometry3::new(
+        ::na::Vector3::new(0.0, 0.0, -radius + ::CONFIG.hazard_spikes_height),
+        ::na::zero(),
+    ) * ::graphics::resizer(radius * 0.8, radius * 0.8, ::CONFIG.hazard_spikes_height);
+
+    create_hazard(
+        pos,
+        half_extents,
+        ::graphics::Primitive::PitCube,
+        ::CONFIG.hazard_spikes_color,
+        primitive_trans,
+        ::component::Hazard::new(None, 0.0),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write_resource(),
+        &world.read_resource(),
+    )
+}
+
+/// The gate is thin along the axis of the corridor and starts at a random time of its period
+pub fn create_laser_gate_w(
+    pos: ::na::Vector3<f32>,
+    radius: f32,
+    axis: ::na::Vector3<f32>,
+    world: &::specs::World,
+) -> ::specs::Entity {
+    let half_extents = axis.map(|a| if a != 0.0 { ::CONFIG.hazard_laser_gate_width } else { radius });
+    let primitive_trans = ::graphics::resizer(half_extents[0], half_extents[1], half_extents[2]);
+
+    let period = (
+        ::CONFIG.hazard_laser_gate_active_time,
+        ::CONFIG.hazard_laser_gate_inactive_time,
+    );
+    let timer = Range::new(0.0, period.0 + period.1).ind_sample(&mut *::util::rng());
+
+    create_hazard(
+        pos,
+        half_extents,
+        ::graphics::Primitive::Cube,
+        ::CONFIG.hazard_laser_gate_color,
+        primitive_trans,
+        ::component::Hazard::new(Some(period), timer),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write_resource(),
+        &world.read_resource(),
+    )
+}
+
+pub fn create_hazard<'a>(
+    pos: ::na::Vector3<f32>,
+    half_extents: ::na::Vector3<f32>,
+    primitive: ::graphics::Primitive,
+    color: ::graphics::Color,
+    primitive_trans: ::na::Transform3<f32>,
+    hazard: ::component::Hazard,
+    hazards: &mut ::specs::WriteStorage<'a, ::component::Hazard>,
+    bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
+    dynamic_draws: &mut ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+    entities: &::specs::Entities,
+) -> ::specs::Entity {
+    // Only the player sensor detects hazards, the player body goes through them
+    let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_static();
+    group.set_membership(&[super::KILLER_GROUP]);
+    group.set_whitelist(&[super::PLAYER_GROUP]);
+
+    let shape = ::ncollide::shape::Cuboid::new(half_extents);
+    let mut body = ::nphysics::object::RigidBody::new_static(shape, 0.0, 0.0);
+    body.set_collision_groups(group);
+    body.set_transformation(::na::Isometry3::new(pos, ::na::zero()));
+
+    let (primitive, groups) = primitive.instantiate();
+
+    let entity = entities.create();
+    dynamic_graphics_assets.insert(
+        entity,
+        ::component::DynamicGraphicsAssets::new(
+            primitive,
+            groups,
+            color,
+            primitive_trans,
+        ),
+    );
+    dynamic_draws.insert(entity, ::component::DynamicDraw);
+    hazards.insert(entity, hazard);
+
+    ::component::PhysicBody::add(entity, body, bodies, physic_world);
+
+    entity
+}
@@ -1,9 +1,9 @@
-use std::collections::HashMap;
+use util::FixedHashMap;
 use std::f32::consts::FRAC_PI_2;
 use std::f32::consts::PI;
 
 pub fn create_2d_maze_walls_w(
-    colors: &HashMap<::na::Vector2<isize>, (::graphics::Color, bool)>,
+    colors: &FixedHashMap<::na::Vector2<isize>, (::graphics::Color, bool)>,
     maze: &::maze::Maze<::na::U2>,
     world: &::specs::World,
 ) {
@@ -20,7 +20,7 @@ pub fn create_2d_maze_walls_w(
 }
 
 pub fn create_2d_maze_walls<'a>(
-    colors: &HashMap<::na::Vector2<isize>, (::graphics::Color, bool)>,
+    colors: &FixedHashMap<::na::Vector2<isize>, (::graphics::Color, bool)>,
     maze: &::maze::Maze<::na::U2>,
     bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
     static_draws: &mut ::specs::WriteStorage<'a, ::component::StaticDraw>,
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use util::FixedHashMap;
 use util::ConvCoord;
 
 pub fn create_3d_maze_walls_w(
-    colors: &HashMap<::na::Vector3<isize>, (::graphics::Color, bool)>,
+    colors: &FixedHashMap<::na::Vector3<isize>, (::graphics::Color, bool)>,
     maze: &::maze::Maze<::na::U3>,
     world: &::specs::World,
 ) {
@@ -18,7 +18,7 @@ pub fn create_3d_maze_walls_w(
 }
 
 pub fn create_3d_maze_walls<'a>(
-    colors: &HashMap<::na::Vector3<isize>, (::graphics::Color, bool)>,
+    colors: &FixedHashMap<::na::Vector3<isize>, (::graphics::Color, bool)>,
     maze: &::maze::Maze<::na::U3>,
     bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
     static_draws: &mut ::specs::WriteStorage<'a, ::component::StaticDraw>,
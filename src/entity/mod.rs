@@ -8,6 +8,7 @@ pub const PLAYER_LASER_GROUP: usize = 7;
 pub const ATTRACTED_VISION_GROUP: usize = 8;
 pub const KILLER_GROUP: usize = 9;
 pub const TELEPORT_GROUP: usize = 10;
+pub const PICKUP_GROUP: usize = 11;
 
 mod depth_ball;
 mod generator;
@@ -22,6 +23,11 @@ mod maze_2d;
 mod maze_3d;
 mod motionless;
 mod attracted;
+mod patroller;
+mod pickup;
+mod door;
+mod hazard;
+mod ghost;
 mod static_draw;
 
 pub use self::static_draw::*;
@@ -38,6 +44,11 @@ pub use self::maze_3d::*;
 pub use self::depth_ball::*;
 pub use self::motionless::*;
 pub use self::attracted::*;
+pub use self::patroller::*;
+pub use self::pickup::*;
+pub use self::door::*;
+pub use self::hazard::*;
+pub use self::ghost::*;
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub enum EntityConf {
@@ -45,12 +56,41 @@ pub enum EntityConf {
     Avoider { eraser: bool },
     Bouncer { eraser: bool },
     MotionLess { eraser: bool },
+    Patroller { eraser: bool },
     Turret,
+    /// Turret with its own settings, the ones missing are the same as `Turret`
+    TurretWith {
+        #[serde(default)]
+        reload_time_ms: Option<usize>,
+        #[serde(default)]
+        range_cm: Option<usize>,
+        /// Percent of depth ball velocity
+        #[serde(default)]
+        projectile_velocity_percent: Option<usize>,
+        /// Percent of player velocity anticipated
+        #[serde(default)]
+        lead_percent: Option<usize>,
+        #[serde(default)]
+        effect: ::component::ProjectileEffect,
+    },
+    /// Any entity controlled by a state machine instead of its own behaviour
+    WithAi {
+        entity: Box<EntityConf>,
+        ai: ::component::AiConf,
+    },
     Generator {
-        generated_entity: ::component::GeneratedEntity,
+        generated_entity: Box<EntityConf>,
         salvo: usize,
         time_between_salvo_ms: usize,
         eraser_probability_percent: usize,
+        #[serde(default)]
+        max_alive: Option<usize>,
+        #[serde(default)]
+        budget: Option<usize>,
+        #[serde(default)]
+        body: bool,
+        #[serde(default)]
+        flocking: bool,
     }
 }
 
@@ -59,32 +99,109 @@ impl EntityConf {
     pub fn is_turret_like(&self) -> bool {
         use self::EntityConf::*;
         match *self {
-            Turret => true,
+            Turret | TurretWith { .. } => true,
+            WithAi { ref entity, .. } => entity.is_turret_like(),
             _ => false,
         }
     }
 
-    pub fn create(&self, pos: ::na::Vector3<f32>, world: &mut ::specs::World) {
+    /// Used to position patrollers along corridors
+    pub fn is_patroller_like(&self) -> bool {
+        use self::EntityConf::*;
+        match *self {
+            Patroller { .. } => true,
+            WithAi { ref entity, .. } => entity.is_patroller_like(),
+            _ => false,
+        }
+    }
+
+    /// Create the entity on a route, entities that doesn't walk a route are created on its
+    /// first position
+    pub fn create_on_route(&self, route: Vec<::na::Vector3<f32>>, world: &mut ::specs::World) -> ::specs::Entity {
+        use self::EntityConf::*;
+        match *self {
+            Patroller { eraser } => create_patroller_w(route, eraser, world),
+            WithAi { ref entity, ref ai } => {
+                let entity = entity.create_on_route(route, world);
+                world.write::<::component::Ai>().insert(entity, ::component::Ai::new(ai.clone()));
+                entity
+            }
+            _ => self.create(route[0], world),
+        }
+    }
+
+    /// Return the same configuration with eraser set if it has one
+    pub fn with_eraser(&self, eraser: bool) -> Self {
+        use self::EntityConf::*;
+        match *self {
+            Attracted { .. } => Attracted { eraser },
+            Avoider { .. } => Avoider { eraser },
+            Bouncer { .. } => Bouncer { eraser },
+            MotionLess { .. } => MotionLess { eraser },
+            Patroller { .. } => Patroller { eraser },
+            WithAi { ref entity, ref ai } => WithAi {
+                entity: Box::new(entity.with_eraser(eraser)),
+                ai: ai.clone(),
+            },
+            ref conf => conf.clone(),
+        }
+    }
+
+    pub fn create(&self, pos: ::na::Vector3<f32>, world: &mut ::specs::World) -> ::specs::Entity {
         use self::EntityConf::*;
         match *self {
             Attracted { eraser } => create_attracted_w(pos, eraser, world),
             Avoider { eraser } => create_avoider_w(pos, eraser, world),
             Bouncer { eraser } => create_bouncer_w(pos, eraser, world),
-            Turret => create_turret_w(pos, world),
+            Turret => TurretWith {
+                reload_time_ms: None,
+                range_cm: None,
+                projectile_velocity_percent: None,
+                lead_percent: None,
+                effect: ::component::ProjectileEffect::Depth,
+            }.create(pos, world),
+            TurretWith {
+                reload_time_ms,
+                range_cm,
+                projectile_velocity_percent,
+                lead_percent,
+                effect,
+            } => create_turret_w(
+                pos,
+                reload_time_ms.map(|t| t as f32 / 1000.0).unwrap_or(::CONFIG.turret_reload_time),
+                range_cm.map(|r| r as f32 / 100.0),
+                projectile_velocity_percent.map(|v| v as f32 / 100.0).unwrap_or(1.0) * ::CONFIG.depth_ball_velocity,
+                lead_percent.map(|l| l as f32 / 100.0).unwrap_or(::CONFIG.turret_lead_factor),
+                effect,
+                world,
+            ),
             MotionLess { eraser } => create_motionless_w(pos, eraser, world),
+            Patroller { eraser } => create_patroller_w(vec![pos], eraser, world),
+            WithAi { ref entity, ref ai } => {
+                let entity = entity.create(pos, world);
+                world.write::<::component::Ai>().insert(entity, ::component::Ai::new(ai.clone()));
+                entity
+            }
             Generator {
-                generated_entity,
+                ref generated_entity,
                 salvo,
                 time_between_salvo_ms,
                 eraser_probability_percent,
-            } => create_generator(
+                max_alive,
+                budget,
+                body,
+                flocking,
+            } => create_generator_w(
                 pos,
-                generated_entity,
+                (**generated_entity).clone(),
                 salvo,
                 time_between_salvo_ms as f32 / 1000.0,
                 eraser_probability_percent as f32 / 100.0,
-                &mut world.write_resource(),
-                &world.read_resource(),
+                max_alive,
+                budget,
+                body,
+                flocking,
+                world,
             ),
         }
     }
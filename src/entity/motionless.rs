@@ -1,4 +1,4 @@
-pub fn create_motionless_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::specs::World) {
+pub fn create_motionless_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::specs::World) -> ::specs::Entity {
     create_motionless(
         pos,
         eraser,
@@ -10,7 +10,7 @@ pub fn create_motionless_w(pos: ::na::Vector3<f32>, eraser: bool, world: &::spec
         &mut world.write(),
         &mut world.write_resource(),
         &world.read_resource(),
-    );
+    )
 }
 
 pub fn create_motionless<'a>(
@@ -24,7 +24,7 @@ pub fn create_motionless<'a>(
     lifes: &mut ::specs::WriteStorage<'a, ::component::Life>,
     physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
     entities: &::specs::Entities,
-) {
+) -> ::specs::Entity {
     let primitive_trans = ::graphics::resizer(
         ::CONFIG.motionless_size,
         ::CONFIG.motionless_size,
@@ -65,4 +65,6 @@ pub fn create_motionless<'a>(
     motionlesses.insert(entity, ::component::Motionless);
 
     ::component::PhysicBody::add(entity, body, bodies, physic_world);
+
+    entity
 }
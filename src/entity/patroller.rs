@@ -0,0 +1,84 @@
+pub fn create_patroller_w(route: Vec<::na::Vector3<f32>>, eraser: bool, world: &::specs::World) -> ::specs::Entity {
+    create_patroller(
+        route,
+        eraser,
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write_resource(),
+        &world.read_resource(),
+    )
+}
+
+pub fn create_patroller<'a>(
+    route: Vec<::na::Vector3<f32>>,
+    eraser: bool,
+    momentums: &mut ::specs::WriteStorage<'a, ::component::Momentum>,
+    patrollers: &mut ::specs::WriteStorage<'a, ::component::Patroller>,
+    bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
+    dynamic_erasers: &mut ::specs::WriteStorage<'a, ::component::DynamicEraser>,
+    dynamic_draws: &mut ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    lifes: &mut ::specs::WriteStorage<'a, ::component::Life>,
+    physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+    entities: &::specs::Entities,
+) -> ::specs::Entity {
+    let primitive_trans = ::graphics::resizer(
+        ::CONFIG.patroller_size,
+        ::CONFIG.patroller_size,
+        ::CONFIG.patroller_size,
+    );
+
+    let shape = ::ncollide::shape::Ball3::new(::CONFIG.patroller_size);
+    let pos = ::na::Isometry3::new(route[0], ::na::zero());
+
+    let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
+    group.set_membership(&[super::ALIVE_GROUP, super::MONSTER_GROUP, super::KILLER_GROUP]);
+
+    let mut body = ::nphysics::object::RigidBody::new_dynamic(shape, 1.0, 0.0, 0.0);
+    let mass = 1.0 / body.inv_mass();
+
+    body.set_transformation(pos);
+    body.set_collision_groups(group);
+
+    let (primitive, groups) = ::graphics::Primitive::Sphere.instantiate();
+
+    let entity = entities.create();
+    patrollers.insert(entity, ::component::Patroller::new(route));
+    momentums.insert(
+        entity,
+        ::component::Momentum::new(
+            mass,
+            ::CONFIG.patroller_velocity,
+            ::CONFIG.patroller_time_to_reach_vmax,
+            None,
+            ::CONFIG.patroller_ang_damping,
+            ::na::zero(),
+            None,
+        ),
+    );
+    dynamic_graphics_assets.insert(
+        entity,
+        ::component::DynamicGraphicsAssets::new(
+            primitive,
+            groups,
+            ::CONFIG.patroller_color,
+            primitive_trans,
+        ),
+    );
+    if eraser {
+        dynamic_erasers.insert(entity, ::component::DynamicEraser);
+        lifes.insert(entity, ::component::Life::EraserAlive);
+    } else {
+        lifes.insert(entity, ::component::Life::DrawAlive);
+        dynamic_draws.insert(entity, ::component::DynamicDraw);
+    }
+
+    ::component::PhysicBody::add(entity, body, bodies, physic_world);
+
+    entity
+}
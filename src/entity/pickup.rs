@@ -0,0 +1,55 @@
+pub fn create_pickup_w(pos: ::na::Vector3<f32>, effect: ::component::PickupEffect, world: &::specs::World) -> ::specs::Entity {
+    create_pickup(
+        pos,
+        effect,
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write(),
+        &mut world.write_resource(),
+        &world.read_resource(),
+    )
+}
+
+pub fn create_pickup<'a>(
+    pos: ::na::Vector3<f32>,
+    effect: ::component::PickupEffect,
+    pickups: &mut ::specs::WriteStorage<'a, ::component::Pickup>,
+    proximitors: &mut ::specs::WriteStorage<'a, ::component::Proximitor>,
+    sensors: &mut ::specs::WriteStorage<'a, ::component::PhysicSensor>,
+    dynamic_draws: &mut ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+    entities: &::specs::Entities,
+) -> ::specs::Entity {
+    let shape = ::ncollide::shape::Ball3::new(::CONFIG.pickup_size);
+
+    let mut group = ::nphysics::object::SensorCollisionGroups::new();
+    group.set_whitelist(&[super::PLAYER_GROUP]);
+    group.set_membership(&[super::PICKUP_GROUP]);
+
+    let mut sensor = ::nphysics::object::Sensor::new(shape, None);
+    sensor.set_relative_position(::na::Isometry3::new(pos, ::na::zero()));
+    sensor.set_collision_groups(group);
+
+    let (primitive, groups) = ::graphics::Primitive::TrianglePyramid.instantiate();
+    let primitive_trans = ::na::Isometry3::new(pos, ::na::zero())
+        * ::graphics::resizer(::CONFIG.pickup_size, ::CONFIG.pickup_size, ::CONFIG.pickup_size);
+
+    let entity = entities.create();
+    pickups.insert(entity, ::component::Pickup { effect });
+    proximitors.insert(entity, ::component::Proximitor::new());
+    dynamic_draws.insert(entity, ::component::DynamicDraw);
+    dynamic_graphics_assets.insert(
+        entity,
+        ::component::DynamicGraphicsAssets::new(
+            primitive,
+            groups,
+            effect.color(),
+            primitive_trans,
+        ),
+    );
+    ::component::PhysicSensor::add(entity, sensor, sensors, physic_world);
+    entity
+}
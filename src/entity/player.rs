@@ -1,7 +1,8 @@
-pub fn create_player_w(pos: ::na::Vector3<f32>, hook: bool, world: &::specs::World) {
+pub fn create_player_w(pos: ::na::Vector3<f32>, hook: Option<::component::HookMode>, weapon: &::component::Weapon, world: &::specs::World) {
     create_player(
         pos,
         hook,
+        weapon,
         &mut world.write(),
         &mut world.write(),
         &mut world.write(),
@@ -20,7 +21,8 @@ pub fn create_player_w(pos: ::na::Vector3<f32>, hook: bool, world: &::specs::Wor
 
 pub fn create_player<'a>(
     pos: ::na::Vector3<f32>,
-    hook: bool,
+    hook: Option<::component::HookMode>,
+    weapon: &::component::Weapon,
     players: &mut ::specs::WriteStorage<'a, ::component::Player>,
     aims: &mut ::specs::WriteStorage<'a, ::component::Aim>,
     momentums: &mut ::specs::WriteStorage<'a, ::component::Momentum>,
@@ -52,7 +54,7 @@ pub fn create_player<'a>(
     players.insert(entity, ::component::Player);
     aims.insert(entity, ::component::Aim::new());
 
-    if hook {
+    if let Some(hook_mode) = hook {
         let (hook_primitive, hook_groups) = ::graphics::Primitive::Hook.instantiate();
         let hook_primitive_trans = ::graphics::resizer(
             ::CONFIG.player_hook_size,
@@ -67,10 +69,15 @@ pub fn create_player<'a>(
                     ::CONFIG.player_hook_color,
                     hook_primitive_trans,
             ));
-        hooks.insert(entity, ::component::Hook::new(::CONFIG.player_hook_force, hook_draw_entity));
+        hooks.insert(entity, ::component::Hook::new(
+            hook_mode,
+            ::CONFIG.player_hook_force,
+            ::CONFIG.player_hook_range,
+            hook_draw_entity,
+        ));
     }
-    let velocity = if hook { ::CONFIG.player_hook_velocity } else { ::CONFIG.player_velocity };
-    let time_to_reach_vmax = if hook { ::CONFIG.player_hook_time_to_reach_vmax } else { ::CONFIG.player_time_to_reach_vmax };
+    let velocity = if hook.is_some() { ::CONFIG.player_hook_velocity } else { ::CONFIG.player_velocity };
+    let time_to_reach_vmax = if hook.is_some() { ::CONFIG.player_hook_time_to_reach_vmax } else { ::CONFIG.player_time_to_reach_vmax };
 
     momentums.insert(
         entity,
@@ -86,6 +93,7 @@ pub fn create_player<'a>(
     );
     super::create_weapon(
         entity,
+        weapon,
         shooters,
         weapon_animations,
         dynamic_huds,
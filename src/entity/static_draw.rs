@@ -37,11 +37,32 @@ pub fn draw_score(pos: ::na::Isometry3<f32>, level: usize, world: &mut ::specs::
         p.push((last[7], 7*3+44, -i*6 - 6));
     }
 
+    let mut p = p.into_iter()
+        .map(|(primitive, dx, dy)| (primitive, dx, dy, ::graphics::Color::Red))
+        .collect::<Vec<_>>();
+
+    // Target times under the boards, the medals obtained are highlighted
+    if let Some(medals) = ::CONFIG.levels.get(level).and_then(|level| level.medals.as_ref()) {
+        let medal = world.read_resource::<::resource::Save>().medal(level);
+        for (i, &(target_medal, target)) in medals.targets().iter().enumerate() {
+            let dy = -(i as isize)*6 - 72;
+            let color = if medal.map(|medal| medal >= target_medal).unwrap_or(false) {
+                ::graphics::Color::Yellow
+            } else {
+                ::graphics::Color::Red
+            };
+            for (j, primitive) in ::graphics::Primitive::from_duration(Some(target)).into_iter().enumerate() {
+                p.push((primitive, j as isize*3 + 4, dy, color));
+            }
+            p.push((target_medal.primitive(), 32, dy, color));
+        }
+    }
+
     let trans: ::na::Transform3<f32> = ::na::Similarity3::from_isometry(pos, radius).to_superset();
 
     let group = ::graphics::Primitive::Text0.reserve(1).remove(0);
 
-    for (primitive, dx, dy) in p {
+    for (primitive, dx, dy, color) in p {
         let local_trans = ::na::Translation3::new(dx as f32 * ::graphics::font::POINT_CENTER_DISTANCE, dy as f32 * ::graphics::font::POINT_CENTER_DISTANCE, 0.0);
         let world_trans = {
             ::graphics::shader::draw1_vs::ty::World {
@@ -55,7 +76,7 @@ pub fn draw_score(pos: ::na::Isometry3<f32>, level: usize, world: &mut ::specs::
             entity,
             primitive.index(),
             group.clone(),
-            ::graphics::Color::Red,
+            color,
             world_trans,
             &mut world.write(),
             &world.read_resource(),
@@ -1,26 +1,44 @@
 use std::f32::consts::FRAC_PI_2;
 
-pub fn create_turret_w(pos: ::na::Vector3<f32>, world: &::specs::World) {
+pub fn create_turret_w(
+    pos: ::na::Vector3<f32>,
+    reload_time: f32,
+    range: Option<f32>,
+    projectile_velocity: f32,
+    lead_factor: f32,
+    effect: ::component::ProjectileEffect,
+    world: &::specs::World,
+) -> ::specs::Entity {
     create_turret(
         pos,
+        reload_time,
+        range,
+        projectile_velocity,
+        lead_factor,
+        effect,
         &mut world.write(),
         &mut world.write(),
         &mut world.write(),
         &mut world.write(),
         &mut world.write_resource(),
         &world.read_resource(),
-    );
+    )
 }
 
 pub fn create_turret<'a>(
     pos: ::na::Vector3<f32>,
+    reload_time: f32,
+    range: Option<f32>,
+    projectile_velocity: f32,
+    lead_factor: f32,
+    effect: ::component::ProjectileEffect,
     turrets: &mut ::specs::WriteStorage<'a, ::component::Turret>,
     bodies: &mut ::specs::WriteStorage<'a, ::component::PhysicBody>,
     dynamic_draws: &mut ::specs::WriteStorage<'a, ::component::DynamicDraw>,
     dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
     physic_world: &mut ::specs::FetchMut<'a, ::resource::PhysicWorld>,
     entities: &::specs::Entities,
-) {
+) -> ::specs::Entity {
     let primitive_trans =
         ::graphics::resizer(::CONFIG.turret_size, ::CONFIG.turret_size, ::CONFIG.turret_size);
 
@@ -44,7 +62,7 @@ pub fn create_turret<'a>(
     let entity = entities.create();
     turrets.insert(
         entity,
-        ::component::Turret::new(::CONFIG.turret_reload_time, pos)
+        ::component::Turret::new(reload_time, pos, range, projectile_velocity, lead_factor, effect)
     );
 
     dynamic_graphics_assets.insert(
@@ -63,4 +81,6 @@ pub fn create_turret<'a>(
         .get_mut(entity)
         .unwrap()
         .ball_in_socket(physic_world, ::na::Point3::from_coordinates(pos));
+
+    entity
 }
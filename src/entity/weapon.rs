@@ -31,8 +31,50 @@ pub fn create_light_ray<'a>(
     reducers.insert(entity, ::component::Reducer::new(::CONFIG.weapon_light_ray_duration, true, true, false));
 }
 
+/// Bullet drawn on the weapon at the given index
+pub fn create_weapon_bullet<'a>(
+    index: usize,
+    color: ::graphics::Color,
+    dynamic_huds: &mut ::specs::WriteStorage<'a, ::component::DynamicHud>,
+    dynamic_graphics_assets: &mut ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+    entities: &::specs::Entities,
+) -> ::specs::Entity {
+    let coef = 3.0;
+    let weapon_pos_y = -0.02 * coef;
+    let weapon_pos_z = -0.016 * coef;
+
+    let bullet_radius = ::CONFIG.weapon_bullet_radius * coef;
+    let bullet_length = ::CONFIG.weapon_bullet_length * coef;
+    let bullet_x = ::CONFIG.weapon_bullet_x * coef;
+    let bullet_dx = ::CONFIG.weapon_bullet_dx * coef;
+
+    let weapon_trans = ::na::Translation3::new(0.0, weapon_pos_y, weapon_pos_z);
+
+    let (primitive, groups) = ::graphics::Primitive::Six.instantiate_unerasable();
+    let primitive_trans = weapon_trans
+        * ::na::Isometry3::new(
+            ::na::Vector3::new(bullet_x + bullet_dx * index as f32, 0.0, 0.0),
+            ::na::Vector3::new(0.0, FRAC_PI_2, 0.0),
+        )
+        * ::graphics::resizer(bullet_radius, bullet_radius, bullet_length);
+
+    let entity = entities.create();
+    dynamic_huds.insert(entity, ::component::DynamicHud);
+    dynamic_graphics_assets.insert(
+        entity,
+        ::component::DynamicGraphicsAssets::new(
+            primitive,
+            groups,
+            color,
+            primitive_trans,
+        ),
+    );
+    entity
+}
+
 pub fn create_weapon<'a>(
     anchor: ::specs::Entity,
+    weapon: &::component::Weapon,
     shooters: &mut ::specs::WriteStorage<'a, ::component::Shooter>,
     weapon_animations: &mut ::specs::WriteStorage<'a, ::component::WeaponAnimation>,
     dynamic_huds: &mut ::specs::WriteStorage<'a, ::component::DynamicHud>,
@@ -55,11 +97,7 @@ pub fn create_weapon<'a>(
     let bar_y_radius = 0.0022 * coef;
     let bar_z_radius = 0.0014 * coef;
 
-    let bullet_radius = ::CONFIG.weapon_bullet_radius * coef;
-    let bullet_length = ::CONFIG.weapon_bullet_length * coef;
-    let bullet_x = ::CONFIG.weapon_bullet_x * coef;
-    let bullet_dx = ::CONFIG.weapon_bullet_dx * coef;
-    let bullet_nbr = ::CONFIG.weapon_bullet_nbr;
+    let bullet_nbr = weapon.bullets;
     let mut bullets = vec![];
 
     let weapon_trans = ::na::Translation3::new(0.0, weapon_pos_y, weapon_pos_z);
@@ -85,26 +123,7 @@ pub fn create_weapon<'a>(
 
         // Bullet
         for i in 0..bullet_nbr {
-            let (primitive, groups) = ::graphics::Primitive::Six.instantiate_unerasable();
-            let primitive_trans = weapon_trans
-                * ::na::Isometry3::new(
-                    ::na::Vector3::new(bullet_x + bullet_dx * i as f32, 0.0, 0.0),
-                    ::na::Vector3::new(0.0, FRAC_PI_2, 0.0),
-                )
-                * ::graphics::resizer(bullet_radius, bullet_radius, bullet_length);
-
-            let entity = entities.create();
-            bullets.push(entity);
-            dynamic_huds.insert(entity, ::component::DynamicHud);
-            dynamic_graphics_assets.insert(
-                entity,
-                ::component::DynamicGraphicsAssets::new(
-                    primitive,
-                    groups,
-                    ::CONFIG.weapon_bullet_color,
-                    primitive_trans,
-                ),
-            );
+            bullets.push(create_weapon_bullet(i, weapon.bullet_color(), dynamic_huds, dynamic_graphics_assets, entities));
         }
         bullets.reverse();
 
@@ -147,6 +166,6 @@ pub fn create_weapon<'a>(
     );
     shooters.insert(
         anchor,
-        ::component::Shooter::new(::CONFIG.weapon_reload_time, bullet_nbr),
+        ::component::Shooter::new(weapon.clone()),
     );
 }
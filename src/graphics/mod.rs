@@ -131,7 +131,7 @@ pub struct Graphics {
 impl Graphics {
     /// This command must be executed before next eraser_sound_system
     pub fn reset_group(&self) {
-        primitives::GROUP_COUNTER.reset();
+        primitives::GROUP_COUNTER.with(|counter| counter.reset());
     }
 
     pub fn framebuffers_and_descriptors(
@@ -574,6 +574,9 @@ pub fn instance_primitives(
     primitives_buffers_def.push(vec![super::font::build_text("ALL".to_string())]);
     primitives_buffers_def.push(vec![super::font::build_text("LAST SCORES".to_string())]);
     primitives_buffers_def.push(vec![super::font::build_text("BEST SCORES".to_string())]);
+    primitives_buffers_def.push(vec![super::font::build_text("BRONZE".to_string())]);
+    primitives_buffers_def.push(vec![super::font::build_text("SILVER".to_string())]);
+    primitives_buffers_def.push(vec![super::font::build_text("GOLD".to_string())]);
 
     let mut final_future = Box::new(now(queue.device().clone())) as Box<GpuFuture>;
     let mut primitives_buffers = vec![];
@@ -626,6 +629,9 @@ pub enum Primitive {
     TextALL,
     TextLastScores,
     TextBestScores,
+    TextBronze,
+    TextSilver,
+    TextGold,
 }
 
 impl Primitive {
@@ -707,27 +713,31 @@ impl Primitive {
             Primitive::TextALL => 1,
             Primitive::TextLastScores => 1,
             Primitive::TextBestScores => 1,
+            Primitive::TextBronze => 1,
+            Primitive::TextSilver => 1,
+            Primitive::TextGold => 1,
         }
     }
 
     pub fn reserve(&self, size: usize) -> Vec<Vec<u16>> {
         let groups_size = self.groups_size();
         (0..size)
-            .map(|_| GROUP_COUNTER.instantiate(groups_size))
+            .map(|_| GROUP_COUNTER.with(|counter| counter.instantiate(groups_size)))
             .collect()
     }
 
     pub fn instantiate(&self) -> (usize, Vec<u16>) {
-        (self.index(), GROUP_COUNTER.instantiate(self.groups_size()))
+        (self.index(), GROUP_COUNTER.with(|counter| counter.instantiate(self.groups_size())))
     }
 
     pub fn instantiate_unerasable(&self) -> (usize, Vec<u16>) {
-        (self.index(), GROUP_COUNTER.instantiate_unerasable(self.groups_size()))
+        (self.index(), GROUP_COUNTER.with(|counter| counter.instantiate_unerasable(self.groups_size())))
     }
 }
 
-lazy_static! {
-    pub static ref GROUP_COUNTER: GroupCounter = GroupCounter::new();
+thread_local! {
+    // Per thread so that runs verified in the background don't take the groups of the game
+    pub static GROUP_COUNTER: GroupCounter = GroupCounter::new();
 }
 
 // WARNING: This value is hard coded in draw2_fs shader !
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use util::FixedHashMap;
 use std::f32::consts::FRAC_PI_2;
 
 pub fn create_hall(world: &mut ::specs::World) {
@@ -14,7 +14,7 @@ pub fn create_hall(world: &mut ::specs::World) {
 
     let maze_size = maze.size.clone();
 
-    let mut maze_colors = HashMap::new();
+    let mut maze_colors = FixedHashMap::default();
 
     // Build Player
     let start_cell = ::na::Vector2::new(maze_size[0] - 3, maze_size[1] - 2);
@@ -23,9 +23,12 @@ pub fn create_hall(world: &mut ::specs::World) {
     let dir = ::na::Vector3::new(0.0, -1.0, 0.0);
     let player_pos = maze.to_world(&start_cell)
         - 0.2 * ::na::Vector3::new(dir[0] as f32, dir[1] as f32, 0.0);
-    world.write_resource::<::resource::PlayerControl>().pointer =
-        [(-dir[1] as f32).atan2(dir[0] as f32), 0.0];
-    ::entity::create_player_w(player_pos, false, world);
+    {
+        let mut player_control = world.write_resource::<::resource::PlayerControl>();
+        player_control.pointer = [(-dir[1] as f32).atan2(dir[0] as f32), 0.0];
+        player_control.can_jump = true;
+    }
+    ::entity::create_player_w(player_pos, None, &::CONFIG.weapon, world);
 
     // Build Teleport
     let teleport_cells = (0isize..levels_on_top)
@@ -34,7 +37,7 @@ pub fn create_hall(world: &mut ::specs::World) {
             .map(|i| (::na::Vector2::new(1, i*3+3), ::na::Vector3::new(0.0, FRAC_PI_2, 0.0))));
 
     for (i, (teleport_cell, teleport_dir)) in teleport_cells.enumerate() {
-        let activated = i == 0 || world.read_resource::<::resource::Save>().score(i-1).is_some();
+        let activated = world.read_resource::<::resource::Save>().level_unlocked(i);
 
         maze_colors.insert(teleport_cell, (::CONFIG.end_color, activated));
         maze.walls.remove(&teleport_cell);
@@ -1,12 +1,25 @@
 use util::ConvCoord;
-use std::collections::HashMap;
+use util::FixedHashMap;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Conf2D {
     pub size: (isize, isize),
     pub percent: f64,
     pub bug: (isize, isize),
-    pub entities: HashMap<::entity::EntityConf, usize>,
+    pub entities: FixedHashMap<::entity::EntityConf, usize>,
+    #[serde(default)]
+    pub pickups: FixedHashMap<::component::PickupEffect, usize>,
+    /// Doors from start to end, each one locks the following part of the maze
+    #[serde(default)]
+    pub doors: Vec<::component::DoorKind>,
+    #[serde(default)]
+    pub hazards: FixedHashMap<::component::HazardKind, usize>,
+    /// Weapon of the player, default one from config if none
+    #[serde(default)]
+    pub weapon: Option<::component::Weapon>,
+    /// Hook of the player, no hook if none
+    #[serde(default)]
+    pub hook: Option<::component::HookMode>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -14,19 +27,41 @@ pub struct Conf3D {
     pub size: (isize, isize, isize),
     pub percent: f64,
     pub bug: (isize, isize, isize),
-    pub entities: HashMap<::entity::EntityConf, usize>,
+    pub entities: FixedHashMap<::entity::EntityConf, usize>,
+    #[serde(default)]
+    pub pickups: FixedHashMap<::component::PickupEffect, usize>,
+    /// Doors from start to end, each one locks the following part of the maze
+    #[serde(default)]
+    pub doors: Vec<::component::DoorKind>,
+    #[serde(default)]
+    pub hazards: FixedHashMap<::component::HazardKind, usize>,
+    /// Weapon of the player, default one from config if none
+    #[serde(default)]
+    pub weapon: Option<::component::Weapon>,
+    /// Hook of the player, no hook if none
+    #[serde(default = "default_hook_3d")]
+    pub hook: Option<::component::HookMode>,
+    /// No gravity, player moves up and down and rolls
+    #[serde(default)]
+    pub zero_gravity: bool,
+}
+
+fn default_hook_3d() -> Option<::component::HookMode> {
+    Some(::component::HookMode::Pull)
 }
 
 // FIXME: factorize
 impl Conf2D {
     pub fn create(&self, world: &mut ::specs::World) {
         let maze = {
-            let (turrets, entities) = self.entities.iter()
-                .fold((0, 0), |mut acc, (e, nbr)| {
+            let (turrets, patrollers, entities) = self.entities.iter()
+                .fold((0, 0, 0), |mut acc, (e, nbr)| {
                     if e.is_turret_like() {
                         acc.0 += nbr;
-                    } else {
+                    } else if e.is_patroller_like() {
                         acc.1 += nbr;
+                    } else {
+                        acc.2 += nbr;
                     }
                     acc
                 });
@@ -35,13 +70,18 @@ impl Conf2D {
                 ::na::Vector2::new(self.size.0, self.size.1),
                 self.percent,
                 ::na::Vector2::new(self.bug.0, self.bug.1),
+                self.doors.len(),
                 turrets,
+                patrollers,
+                self.pickups.values().sum(),
+                self.hazards.get(&::component::HazardKind::Spikes).cloned().unwrap_or(0),
+                self.hazards.get(&::component::HazardKind::LaserGate).cloned().unwrap_or(0),
                 entities,
             )
         };
 
         // Build walls
-        let mut maze_colors = HashMap::new();
+        let mut maze_colors = FixedHashMap::default();
         maze_colors.insert(maze.start_cell, (::CONFIG.start_color, false));
         maze_colors.insert(maze.end_cell, (::CONFIG.end_color, true));
 
@@ -62,9 +102,12 @@ impl Conf2D {
         let dir = maze.start_opening - maze.start_cell;
         let player_pos = maze.maze.to_world(&maze.start_cell)
             - 0.2 * ::na::Vector3::new(dir[0] as f32, dir[1] as f32, 0.0);
-        world.write_resource::<::resource::PlayerControl>().pointer =
-            [(-dir[1] as f32).atan2(dir[0] as f32), 0.0];
-        ::entity::create_player_w(player_pos, false, world);
+        {
+            let mut player_control = world.write_resource::<::resource::PlayerControl>();
+            player_control.pointer = [(-dir[1] as f32).atan2(dir[0] as f32), 0.0];
+            player_control.can_jump = true;
+        }
+        ::entity::create_player_w(player_pos, self.hook, self.weapon.as_ref().unwrap_or(&::CONFIG.weapon), world);
 
         // Build turrets
         self.entities.iter()
@@ -80,19 +123,55 @@ impl Conf2D {
                 conf.create(pos, world);
             });
 
+        // Build pickups
+        self.pickups.iter()
+            .flat_map(|(&effect, &nbr)| {
+                let mut v = vec![];
+                v.resize(nbr, effect);
+                v
+            })
+            .zip(maze.pickup_cells.iter())
+            .for_each(|(effect, cell)| {
+                let pos = maze.maze.to_world(cell);
+                ::entity::create_pickup_w(pos, effect, world);
+            });
+
+        // Build patrollers
+        let patrollers = self.entities.iter()
+            .filter(|&(e, _)| e.is_patroller_like())
+            .flat_map(|(e, &nbr)| {
+                let mut v = vec![];
+                v.resize(nbr, e);
+                v
+            })
+            .zip(maze.patroller_routes.iter())
+            .map(|(conf, route)| {
+                let world_route = route.iter().map(|cell| maze.maze.to_world(cell)).collect();
+                (conf.create_on_route(world_route, world), route[0])
+            })
+            .collect::<Vec<_>>();
+
         // Build entities
-        self.entities.iter()
-            .filter(|&(e, _)| !e.is_turret_like())
+        let monsters = self.entities.iter()
+            .filter(|&(e, _)| !e.is_turret_like() && !e.is_patroller_like())
             .flat_map(|(e, &nbr)| {
                 let mut v = vec![];
                 v.resize(nbr, e);
                 v
             })
             .zip(maze.entity_cells.iter())
-            .for_each(|(conf, cell)| {
+            .map(|(conf, cell)| {
                 let pos = maze.maze.to_world(cell);
-                conf.create(pos, world);
-            });
+                (conf.create(pos, world), *cell)
+            })
+            .chain(patrollers)
+            .collect::<Vec<_>>();
+
+        // Build doors
+        maze.create_doors(&self.doors, &monsters, world);
+
+        // Build hazards
+        maze.create_hazards(world);
 
         // Build maze resource
         world.add_resource(::resource::Maze::Maze2D(maze.maze));
@@ -102,12 +181,14 @@ impl Conf2D {
 impl Conf3D {
     pub fn create(&self, world: &mut ::specs::World) {
         let maze = {
-            let (turrets, entities) = self.entities.iter()
-                .fold((0, 0), |mut acc, (e, nbr)| {
+            let (turrets, patrollers, entities) = self.entities.iter()
+                .fold((0, 0, 0), |mut acc, (e, nbr)| {
                     if e.is_turret_like() {
                         acc.0 += nbr;
-                    } else {
+                    } else if e.is_patroller_like() {
                         acc.1 += nbr;
+                    } else {
+                        acc.2 += nbr;
                     }
                     acc
                 });
@@ -116,13 +197,18 @@ impl Conf3D {
                 ::na::Vector3::new(self.size.0, self.size.1, self.size.2),
                 self.percent,
                 ::na::Vector3::new(self.bug.0, self.bug.1, self.bug.2),
+                self.doors.len(),
                 turrets,
+                patrollers,
+                self.pickups.values().sum(),
+                self.hazards.get(&::component::HazardKind::Spikes).cloned().unwrap_or(0),
+                self.hazards.get(&::component::HazardKind::LaserGate).cloned().unwrap_or(0),
                 entities,
             )
         };
 
         // Build walls
-        let mut maze_colors = HashMap::new();
+        let mut maze_colors = FixedHashMap::default();
         maze_colors.insert(maze.start_cell, (::CONFIG.start_color, false));
         maze_colors.insert(maze.end_cell, (::CONFIG.end_color, true));
 
@@ -143,9 +229,13 @@ impl Conf3D {
         let dir = maze.start_opening - maze.start_cell;
         let player_pos = maze.maze.to_world(&maze.start_cell)
             - 0.2 * ::na::Vector3::new(dir[0] as f32, dir[1] as f32, 0.0);
-        world.write_resource::<::resource::PlayerControl>().pointer =
-            [(-dir[1] as f32).atan2(dir[0] as f32), 0.0];
-        ::entity::create_player_w(player_pos, true, world);
+        {
+            let mut player_control = world.write_resource::<::resource::PlayerControl>();
+            player_control.pointer = [(-dir[1] as f32).atan2(dir[0] as f32), 0.0];
+            player_control.zero_gravity = self.zero_gravity;
+            player_control.reset_orientation();
+        }
+        ::entity::create_player_w(player_pos, self.hook, self.weapon.as_ref().unwrap_or(&::CONFIG.weapon), world);
 
         // Build turrets
         self.entities.iter()
@@ -161,19 +251,55 @@ impl Conf3D {
                 conf.create(pos, world);
             });
 
+        // Build pickups
+        self.pickups.iter()
+            .flat_map(|(&effect, &nbr)| {
+                let mut v = vec![];
+                v.resize(nbr, effect);
+                v
+            })
+            .zip(maze.pickup_cells.iter())
+            .for_each(|(effect, cell)| {
+                let pos = maze.maze.to_world(cell);
+                ::entity::create_pickup_w(pos, effect, world);
+            });
+
+        // Build patrollers
+        let patrollers = self.entities.iter()
+            .filter(|&(e, _)| e.is_patroller_like())
+            .flat_map(|(e, &nbr)| {
+                let mut v = vec![];
+                v.resize(nbr, e);
+                v
+            })
+            .zip(maze.patroller_routes.iter())
+            .map(|(conf, route)| {
+                let world_route = route.iter().map(|cell| maze.maze.to_world(cell)).collect();
+                (conf.create_on_route(world_route, world), route[0])
+            })
+            .collect::<Vec<_>>();
+
         // Build entities
-        self.entities.iter()
-            .filter(|&(e, _)| !e.is_turret_like())
+        let monsters = self.entities.iter()
+            .filter(|&(e, _)| !e.is_turret_like() && !e.is_patroller_like())
             .flat_map(|(e, &nbr)| {
                 let mut v = vec![];
                 v.resize(nbr, e);
                 v
             })
             .zip(maze.entity_cells.iter())
-            .for_each(|(conf, cell)| {
+            .map(|(conf, cell)| {
                 let pos = maze.maze.to_world(cell);
-                conf.create(pos, world);
-            });
+                (conf.create(pos, world), *cell)
+            })
+            .chain(patrollers)
+            .collect::<Vec<_>>();
+
+        // Build doors
+        maze.create_doors(&self.doors, &monsters, world);
+
+        // Build hazards
+        maze.create_hazards(world);
 
         // Build maze resource
         world.add_resource(::resource::Maze::Maze3D(maze.maze));
@@ -2,6 +2,8 @@ pub mod kill_all_kruskal;
 use rand::distributions::{IndependentSample, Range};
 use std::ops::Mul;
 use std::hash::Hash;
+use std::time::Duration;
+use util::FixedHashSet;
 use typenum;
 
 mod hall;
@@ -20,6 +22,64 @@ impl Level {
             Level::KillAllKruskal3D(ref conf) => conf.create(world),
         }
     }
+
+    pub fn is_3d(&self) -> bool {
+        match *self {
+            Level::KillAllKruskal2D(_) => false,
+            Level::KillAllKruskal3D(_) => true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Medal {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl Medal {
+    pub fn primitive(&self) -> ::graphics::Primitive {
+        match *self {
+            Medal::Bronze => ::graphics::Primitive::TextBronze,
+            Medal::Silver => ::graphics::Primitive::TextSilver,
+            Medal::Gold => ::graphics::Primitive::TextGold,
+        }
+    }
+}
+
+/// Parts of a level played in a row
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LevelConf {
+    pub parts: Vec<Level>,
+    /// Target times shown on the score board of the level in the hall
+    #[serde(default)]
+    pub medals: Option<Medals>,
+}
+
+/// Target durations of a level in seconds
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Medals {
+    pub bronze: f32,
+    pub silver: f32,
+    pub gold: f32,
+}
+
+impl Medals {
+    /// Targets from the best medal to the worst
+    pub fn targets(&self) -> [(Medal, Duration); 3] {
+        [
+            (Medal::Gold, ::util::duration_from_secs(self.gold)),
+            (Medal::Silver, ::util::duration_from_secs(self.silver)),
+            (Medal::Bronze, ::util::duration_from_secs(self.bronze)),
+        ]
+    }
+
+    pub fn medal(&self, time: Duration) -> Option<Medal> {
+        self.targets().iter()
+            .find(|&&(_, target)| time <= target)
+            .map(|&(medal, _)| medal)
+    }
 }
 
 pub struct KruskalDecorated<D>
@@ -35,6 +95,17 @@ where
     end_opening: ::na::VectorN<isize, D>,
     entity_cells: Vec<::na::VectorN<isize, D>>,
     turret_cells: Vec<::na::VectorN<isize, D>>,
+    patroller_routes: Vec<Vec<::na::VectorN<isize, D>>>,
+    pickup_cells: Vec<::na::VectorN<isize, D>>,
+    /// Doors in the order they are met from start
+    door_cells: Vec<::na::VectorN<isize, D>>,
+    /// Cells between the previous door and the door
+    door_regions: Vec<FixedHashSet<::na::VectorN<isize, D>>>,
+    /// Cell of the switch or key of the door
+    door_objective_cells: Vec<::na::VectorN<isize, D>>,
+    spikes_cells: Vec<::na::VectorN<isize, D>>,
+    /// Gate cells with the direction of their corridor
+    laser_gate_cells: Vec<(::na::VectorN<isize, D>, ::na::VectorN<isize, D>)>,
 }
 
 impl<D> KruskalDecorated<D>
@@ -47,9 +118,13 @@ where
     /// then end room the further from start
     /// in rooms cells we put turret exept in front of end and start room
     /// in all cells exept turret and start room we put entities
+    /// in dead rooms we put pickups
+    /// in corridors we put patrollers routes
+    /// on chokepoints of the path from start to end we put doors
+    /// in inner room cells we put spikes, rooms can be walked around them
+    /// in corridors we put laser gates
     /// and all other things
-    pub fn new(size: ::na::VectorN<isize, D>, percent: f64, bug: ::na::VectorN<isize, D>, turrets: usize, entities: usize) -> Self {
-        let mut rng = ::rand::thread_rng();
+    pub fn new(size: ::na::VectorN<isize, D>, percent: f64, bug: ::na::VectorN<isize, D>, doors: usize, turrets: usize, patrollers: usize, pickups: usize, spikes: usize, laser_gates: usize, entities: usize) -> Self {
         loop {
             // Generate general maze
             let mut maze = ::maze::Maze::kruskal(size.clone(), percent, bug.clone(), 1.0);
@@ -72,6 +147,60 @@ where
             if dig_end.first().is_none() { continue }
             let (end_cell, end_opening) = dig_end.remove(0);
 
+            // Put doors evenly along chokepoints
+            let mut chokepoints = if doors > 0 {
+                maze.chokepoints(start_cell.clone(), end_cell.clone())
+            } else {
+                vec![]
+            };
+            chokepoints.retain(|cell| *cell != start_opening && *cell != end_opening);
+
+            let mut door_cells = vec![];
+            if !chokepoints.is_empty() {
+                for i in 0..doors {
+                    door_cells.push(chokepoints[(i + 1) * chokepoints.len() / (doors + 1)].clone());
+                }
+                door_cells.dedup();
+            }
+
+            // Regions are opened one after the other
+            maze.doors = door_cells.iter().cloned().collect();
+            let mut door_regions = vec![];
+            let mut previous_reachable = FixedHashSet::default();
+            for door in &door_cells {
+                let reachable = maze.reachable_cells(start_cell.clone());
+                let mut region = reachable.difference(&previous_reachable).cloned().collect::<FixedHashSet<_>>();
+                region.retain(|cell| !door_cells.contains(cell));
+                door_regions.push(region);
+                maze.doors.remove(door);
+                previous_reachable = reachable;
+            }
+            maze.doors = door_cells.iter().cloned().collect();
+
+            let mut door_objective_cells = vec![];
+            for region in &door_regions {
+                let mut candidates = region.iter()
+                    .filter(|cell| {
+                        **cell != start_cell
+                        && **cell != start_opening
+                        && !door_objective_cells.contains(*cell)
+                        && maze.is_neighbouring_wall(cell)
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if candidates.is_empty() {
+                    break;
+                }
+                let index = Range::new(0, candidates.len()).ind_sample(&mut *::util::rng());
+                door_objective_cells.push(candidates.swap_remove(index));
+            }
+            if door_objective_cells.len() != door_regions.len() { continue }
+
+            let reserved = door_cells.iter()
+                .chain(door_objective_cells.iter())
+                .cloned()
+                .collect::<FixedHashSet<_>>();
+
             // Put turrets
             let cells = maze.compute_inner_room_zones()
                 .iter()
@@ -83,13 +212,14 @@ where
                         && *cell != start_opening
                         && *cell != end_cell
                         && *cell != end_opening
+                        && !reserved.contains(cell)
                         && maze.is_neighbouring_wall(cell)
                     });
                     if room.is_empty() {
                         None
                     } else {
                         let cell = room.iter()
-                            .skip(Range::new(0, room.len()).ind_sample(&mut rng))
+                            .skip(Range::new(0, room.len()).ind_sample(&mut *::util::rng()))
                             .next()
                             .unwrap()
                             .clone();
@@ -104,6 +234,87 @@ where
                 turret_cells.push(cell);
             }
 
+            // Put pickups
+            let mut dead_rooms = maze.compute_dead_room_zones();
+            for room in &mut dead_rooms {
+                room.retain(|cell| {
+                    *cell != start_cell
+                    && *cell != start_opening
+                    && *cell != end_cell
+                    && *cell != end_opening
+                    && !turret_cells.contains(cell)
+                    && !reserved.contains(cell)
+                });
+            }
+            dead_rooms.retain(|room| !room.is_empty());
+            ::rand::Rng::shuffle(&mut *::util::rng(), &mut dead_rooms);
+
+            let mut pickup_cells = vec![];
+
+            for room in dead_rooms.iter().take(pickups) {
+                let cell = room.iter()
+                    .skip(Range::new(0, room.len()).ind_sample(&mut *::util::rng()))
+                    .next()
+                    .unwrap()
+                    .clone();
+                pickup_cells.push(cell);
+            }
+
+            // Put hazards
+            let (spikes_cells, laser_gate_cells) = {
+                let is_free = |cell: &::na::VectorN<isize, D>| {
+                    (start_cell.clone() - cell.clone()).iter().fold(0, |acc, c| acc + c.pow(2)) > 5_isize.pow(2)
+                    && *cell != end_cell
+                    && *cell != end_opening
+                    && !reserved.contains(cell)
+                    && !turret_cells.contains(cell)
+                    && !pickup_cells.contains(cell)
+                };
+
+                let mut room_cells = maze.compute_inner_room_zones()
+                    .iter()
+                    .flat_map(|room| room.iter().cloned())
+                    .filter(|cell| is_free(cell))
+                    .collect::<Vec<_>>();
+
+                let mut spikes_cells = vec![];
+                for _ in 0..spikes {
+                    if room_cells.is_empty() {
+                        break
+                    }
+                    let index = Range::new(0, room_cells.len()).ind_sample(&mut *::util::rng());
+                    spikes_cells.push(room_cells.swap_remove(index));
+                }
+
+                let mut corridor_cells = maze.compute_corridor_zones()
+                    .iter()
+                    .flat_map(|corridor| corridor.iter().cloned())
+                    .filter(|cell| is_free(cell))
+                    .collect::<Vec<_>>();
+
+                let mut laser_gate_cells = vec![];
+                for _ in 0..laser_gates {
+                    if corridor_cells.is_empty() {
+                        break
+                    }
+                    let index = Range::new(0, corridor_cells.len()).ind_sample(&mut *::util::rng());
+                    let cell = corridor_cells.swap_remove(index);
+                    let axis = maze.neighbours
+                        .iter()
+                        .find(|&n| !maze.walls.contains(&(n + cell.clone())))
+                        .cloned()
+                        .unwrap_or(::na::zero());
+                    laser_gate_cells.push((cell, axis));
+                }
+
+                (spikes_cells, laser_gate_cells)
+            };
+
+            maze.hazards = spikes_cells.iter()
+                .chain(laser_gate_cells.iter().map(|&(ref cell, _)| cell))
+                .cloned()
+                .collect();
+
             // Put entities
             let mut cells = maze.iterate_maze();
             cells.retain(|cell| {
@@ -114,8 +325,17 @@ where
                 && *cell != end_cell
                 && *cell != end_opening
                 && !turret_cells.contains(cell)
+                && !pickup_cells.contains(cell)
+                && !reserved.contains(cell)
+                && !maze.hazards.contains(cell)
             });
 
+            // Not enough dead rooms
+            while pickup_cells.len() < pickups && !cells.is_empty() {
+                let index = Range::new(0, cells.len()).ind_sample(&mut *::util::rng());
+                pickup_cells.push(cells.swap_remove(index));
+            }
+
             let mut entity_cells = vec![];
 
             for _ in 0..entities {
@@ -123,11 +343,41 @@ where
                     break
                 }
 
-                let index = Range::new(0, cells.len()).ind_sample(&mut rng);
+                let index = Range::new(0, cells.len()).ind_sample(&mut *::util::rng());
                 let cell = cells.swap_remove(index);
                 entity_cells.push(cell);
             }
 
+            // Put patrollers
+            let mut corridors = maze.compute_corridor_zones();
+            for corridor in &mut corridors {
+                corridor.retain(|cell| {
+                    (start_cell.clone() - cell.clone()).iter().fold(0, |acc, c| acc + c.pow(2)) > 5_isize.pow(2)
+                    && *cell != start_cell
+                    && *cell != start_opening
+                    && *cell != end_cell
+                    && *cell != end_opening
+                    && !reserved.contains(cell)
+                });
+            }
+            corridors.retain(|corridor| corridor.len() > 1);
+
+            let mut patroller_routes = vec![];
+
+            for i in 0..patrollers {
+                if let Some(corridor) = corridors.get(i % corridors.len().max(1)) {
+                    let start = corridor.iter()
+                        .skip(Range::new(0, corridor.len()).ind_sample(&mut *::util::rng()))
+                        .next()
+                        .unwrap()
+                        .clone();
+                    patroller_routes.push(maze.zone_loop(corridor, start));
+                } else if !cells.is_empty() {
+                    let index = Range::new(0, cells.len()).ind_sample(&mut *::util::rng());
+                    patroller_routes.push(vec![cells.swap_remove(index)]);
+                }
+            }
+
             break KruskalDecorated {
                 maze,
                 start_cell,
@@ -136,7 +386,56 @@ where
                 end_opening,
                 entity_cells,
                 turret_cells,
+                patroller_routes,
+                pickup_cells,
+                door_cells,
+                door_regions,
+                door_objective_cells,
+                spikes_cells,
+                laser_gate_cells,
+            }
+        }
+    }
+
+    /// Create doors with their switches and keys, clear doors wait for the monsters
+    /// created in their region
+    pub fn create_doors(
+        &self,
+        kinds: &[::component::DoorKind],
+        monsters: &[(::specs::Entity, ::na::VectorN<isize, D>)],
+        world: &mut ::specs::World,
+    ) {
+        use component::{DoorKind, DoorLock};
+
+        for (i, (kind, door)) in kinds.iter().zip(self.door_cells.iter()).enumerate() {
+            let objective = self.maze.to_world(&self.door_objective_cells[i]);
+            let lock = match *kind {
+                DoorKind::Switch => DoorLock::Switch(::entity::create_door_switch_w(objective, world)),
+                DoorKind::Clear => DoorLock::Clear(
+                    monsters.iter()
+                        .filter(|&&(_, ref cell)| self.door_regions[i].contains(cell))
+                        .map(|&(entity, _)| entity)
+                        .collect()
+                ),
+                DoorKind::Key => {
+                    ::entity::create_pickup_w(objective, ::component::PickupEffect::Key(i), world);
+                    DoorLock::Key(i)
+                }
+            };
+            ::entity::create_door_w(self.maze.to_world(door), self.maze.scale / 2.0, lock, world);
+        }
+    }
+
+    pub fn create_hazards(&self, world: &mut ::specs::World) {
+        for cell in &self.spikes_cells {
+            ::entity::create_spikes_w(self.maze.to_world(cell), self.maze.scale / 2.0, world);
+        }
+        for &(ref cell, ref axis) in &self.laser_gate_cells {
+            let mut world_axis: ::na::Vector3<f32> = ::na::zero();
+            for i in 0..D::dim() {
+                world_axis[i] = axis[i] as f32;
             }
+            ::entity::create_laser_gate_w(self.maze.to_world(cell), self.maze.scale / 2.0, world_axis, world);
         }
     }
 }
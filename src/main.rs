@@ -12,6 +12,7 @@ extern crate pathfinding;
 extern crate png;
 extern crate rand;
 extern crate ron;
+extern crate serde_json;
 #[macro_use]
 extern crate serde_derive;
 extern crate specs;
@@ -38,6 +39,11 @@ mod entity;
 mod component;
 mod system;
 mod resource;
+mod replay;
+mod stats;
+mod achievement;
+mod score_export;
+mod verify;
 pub mod maze;
 mod config;
 mod level;
@@ -92,10 +98,125 @@ fn init_imgui() -> ::imgui::ImGui {
     imgui
 }
 
+fn register_components(world: &mut ::specs::World) {
+    world.register::<::component::Player>();
+    world.register::<::component::Teleport>();
+    world.register::<::component::Pickup>();
+    world.register::<::component::Door>();
+    world.register::<::component::Hazard>();
+    world.register::<::component::Ghost>();
+    world.register::<::component::Flocking>();
+    world.register::<::component::Ai>();
+    world.register::<::component::Generator>();
+    world.register::<::component::Generated>();
+    world.register::<::component::Shooter>();
+    world.register::<::component::Hook>();
+    world.register::<::component::WeaponAnimation>();
+    world.register::<::component::Aim>();
+    world.register::<::component::StaticDraw>();
+    world.register::<::component::DynamicDraw>();
+    world.register::<::component::DynamicEraser>();
+    world.register::<::component::DynamicHud>();
+    world.register::<::component::DynamicGraphicsAssets>();
+    world.register::<::component::DeletBool>();
+    world.register::<::component::DeletTimer>();
+    world.register::<::component::Reducer>();
+    world.register::<::component::PhysicBody>();
+    world.register::<::component::Activated>();
+    world.register::<::component::Momentum>();
+    world.register::<::component::Avoider>();
+    world.register::<::component::Bouncer>();
+    world.register::<::component::Turret>();
+    world.register::<::component::DepthBall>();
+    world.register::<::component::Attracted>();
+    world.register::<::component::Patroller>();
+    world.register::<::component::Motionless>();
+    world.register::<::component::Life>();
+    world.register::<::component::Contactor>();
+    world.register::<::component::Proximitor>();
+    world.register::<::component::FollowPlayer>();
+    world.register::<::component::PhysicSensor>();
+}
+
+/// Systems updating the game, the headless dispatcher doesn't play sounds nor read the menu
+/// inputs
+fn game_update_dispatcher<'a, 'b>(headless: bool) -> ::specs::Dispatcher<'a, 'b> {
+    let mut builder = ::specs::DispatcherBuilder::new();
+    if !headless {
+        builder = builder
+            .add(::system::AudioSystem, "audio", &[])
+            .add(::system::MenuGameControlSystem, "menu_game", &[]);
+    }
+    builder
+        .add(::system::PlayerControlSystem, "player_control", &[])
+        .add(::system::AvoiderControlSystem, "avoider_control", &[])
+        .add(::system::BouncerControlSystem, "bouncer_control", &[])
+        // Systems using the game random generator are ordered so that updates are deterministic
+        .add(::system::AiSystem::new(), "ai", &["avoider_control"])
+        .add(::system::FlockingSystem::new(), "flocking", &["avoider_control", "bouncer_control", "ai"])
+        .add(::system::TeleportSystem, "teleport", &[])
+        .add(::system::PickupSystem, "pickup", &[])
+        .add(::system::DoorSystem, "door", &[])
+        .add(::system::HazardSystem, "hazard", &[])
+        .add(::system::GhostSystem, "ghost", &["player_control"])
+        .add(::system::FollowPlayerSystem, "follower_control", &[])
+        .add(::system::TurretControlSystem::new(), "turret_control", &[])
+        .add(::system::GeneratorSystem, "generator", &["ai"])
+        .add(::system::ShootSystem::new(), "shoot", &[])
+        .add(::system::HookSystem::new(), "hook", &[])
+        .add(::system::PhysicSystem, "physic", &[])
+        .add(::system::DeleterSystem, "deleter", &[])
+        .add(::system::PlayerDeathSystem, "death", &[])
+        .add(::system::ActivateSystem, "activate", &[])
+        .add(::system::ReducerSystem, "reducer", &[])
+        .add(::system::DepthCoefSystem, "depth_coef", &[])
+        .add(::system::DepthBallSystem, "depth_ball", &[])
+        .add(::system::AttractedSystem::new(), "attracted", &["generator"])
+        .add(::system::PatrollerSystem::new(), "patroller", &[])
+        .add_barrier() // following systems will delete physic bodies
+        .add(::system::LifeSystem, "life", &[])
+        .build()
+}
+
+/// Run the scores command given on the command line on the current profile, return false if
+/// there is none
+fn scores_command() -> bool {
+    let args = ::std::env::args().skip(1).collect::<Vec<_>>();
+    let (command, path) = match (args.get(0), args.get(1)) {
+        (Some(command), Some(path)) => (command.clone(), ::std::path::PathBuf::from(path)),
+        _ => return false,
+    };
+    let mut save = ::resource::Save::new();
+    let result = match &command[..] {
+        "--export-scores" => ::score_export::export(&save, &path)
+            .map(|()| format!("Scores of profile {} exported to {}", save.profile(), path.display())),
+        "--import-scores" => ::score_export::import(&mut save, &path)
+            .map(|kept| format!("Scores of {} imported in profile {}, {} runs kept", path.display(), save.profile(), kept)),
+        _ => return false,
+    };
+    match result {
+        Ok(message) => println!("{}", message),
+        Err(e) => {
+            eprintln!("Failed: {}", e);
+            ::std::process::exit(1);
+        }
+    }
+    true
+}
+
 fn main() {
+    // Scores can be exported and imported without launching the game
+    if scores_command() {
+        return;
+    }
+
     // On windows stack is overflowed otherwise
     ::std::thread::Builder::new().stack_size(32 * 1024 * 1024).spawn(|| {
-        while let ControlFlow::Restart = new_game() {}
+        // Profile is picked at startup only, not when restarting after a setting change
+        let mut pick_profile = true;
+        while let ControlFlow::Restart = new_game(pick_profile) {
+            pick_profile = false;
+        }
     }).unwrap().join().unwrap();
 }
 
@@ -104,12 +225,19 @@ enum ControlFlow {
     Quit,
 }
 
-fn new_game() -> ControlFlow {
+fn new_game(pick_profile: bool) -> ControlFlow {
     ::std::env::set_var("WINIT_UNIX_BACKEND", "x11");
     let mut save = ::resource::Save::new();
 
     let text = ::resource::Text::load();
 
+    if let Some(path) = save.quarantined() {
+        ::show_message::show(format!("{}\n\n{}", text.corrupt_save, path.display()));
+    }
+    if let Some(path) = save.newer_backup() {
+        ::show_message::show(format!("{}\n\n{}", text.newer_save, path.display()));
+    }
+
     let instance = {
         let extensions = vulkano_win::required_extensions();
         let info = app_info_from_cargo_toml!();
@@ -157,35 +285,7 @@ fn new_game() -> ControlFlow {
     let debug = ::std::env::var("HYPERZEN_TRAINING_DEBUG").map(|v| v == "1").unwrap_or(false);
 
     let mut world = specs::World::new();
-    world.register::<::component::Player>();
-    world.register::<::component::Teleport>();
-    world.register::<::component::Generator>();
-    world.register::<::component::Shooter>();
-    world.register::<::component::Hook>();
-    world.register::<::component::WeaponAnimation>();
-    world.register::<::component::Aim>();
-    world.register::<::component::StaticDraw>();
-    world.register::<::component::DynamicDraw>();
-    world.register::<::component::DynamicEraser>();
-    world.register::<::component::DynamicHud>();
-    world.register::<::component::DynamicGraphicsAssets>();
-    world.register::<::component::DeletBool>();
-    world.register::<::component::DeletTimer>();
-    world.register::<::component::Reducer>();
-    world.register::<::component::PhysicBody>();
-    world.register::<::component::Activated>();
-    world.register::<::component::Momentum>();
-    world.register::<::component::Avoider>();
-    world.register::<::component::Bouncer>();
-    world.register::<::component::Turret>();
-    world.register::<::component::DepthBall>();
-    world.register::<::component::Attracted>();
-    world.register::<::component::Motionless>();
-    world.register::<::component::Life>();
-    world.register::<::component::Contactor>();
-    world.register::<::component::Proximitor>();
-    world.register::<::component::FollowPlayer>();
-    world.register::<::component::PhysicSensor>();
+    register_components(&mut world);
     world.add_resource(::resource::Help(String::new()));
     world.add_resource(text);
     world.add_resource(graphics.clone());
@@ -198,12 +298,24 @@ fn new_game() -> ControlFlow {
     world.add_resource(::resource::PlayerControl::new());
     world.add_resource(::resource::Benchmarks::new());
     world.add_resource(::resource::UpdateTime(0.0));
+    world.add_resource(::resource::TimeScale::new());
     world.add_resource(::resource::GameDuration(Duration::new(0, 0)));
+    world.add_resource(::resource::LevelSeed(0));
+    world.add_resource(::resource::Interpolation::new());
+    world.add_resource(::resource::Replays::new(save.profile()));
+    world.add_resource(::resource::Stats::new());
+    world.add_resource(::resource::Achievements::new());
+    world.add_resource(::resource::Verifier::new(graphics.clone()));
     world.add_resource(::resource::Activated(false));
     world.add_resource(::resource::Audio::init(&save));
     world.add_resource(::resource::LevelActions(vec![]));
+    world.add_resource(::resource::GameEvents(vec![]));
+    world.add_resource(::resource::GeneratorSpawns(vec![]));
     world.add_resource(::resource::ErasedStatus::new());
-    let menu_state = ::resource::MenuState::new(&save);
+    let mut menu_state = ::resource::MenuState::new(&save);
+    if pick_profile && menu_state.profiles.len() > 1 {
+        menu_state.state = ::resource::MenuStateState::Profiles;
+    }
     world.add_resource(save);
     world.add_resource(menu_state);
     world.maintain();
@@ -217,28 +329,13 @@ fn new_game() -> ControlFlow {
         .add(::system::AudioSystem, "audio", &[])
         .build();
 
-    let mut game_update_dispatcher = ::specs::DispatcherBuilder::new()
-        .add(::system::AudioSystem, "audio", &[])
-        .add(::system::MenuGameControlSystem, "menu_game", &[])
-        .add(::system::PlayerControlSystem, "player_control", &[])
-        .add(::system::AvoiderControlSystem, "avoider_control", &[])
-        .add(::system::BouncerControlSystem, "bouncer_control", &[])
-        .add(::system::TeleportSystem, "teleport", &[])
-        .add(::system::FollowPlayerSystem, "follower_control", &[])
-        .add(::system::TurretControlSystem::new(), "turret_control", &[])
-        .add(::system::GeneratorSystem, "generator", &[])
-        .add(::system::ShootSystem::new(), "shoot", &[])
-        .add(::system::HookSystem::new(), "hook", &[])
-        .add(::system::PhysicSystem, "physic", &[])
-        .add(::system::DeleterSystem, "deleter", &[])
-        .add(::system::PlayerDeathSystem, "death", &[])
-        .add(::system::ActivateSystem, "activate", &[])
-        .add(::system::ReducerSystem, "reducer", &[])
-        .add(::system::DepthCoefSystem, "depth_coef", &[])
-        .add(::system::DepthBallSystem, "depth_ball", &[])
-        .add(::system::AttractedSystem::new(), "attracted", &[])
-        .add_barrier() // following systems will delete physic bodies
-        .add(::system::LifeSystem, "life", &[])
+    let mut game_update_dispatcher = game_update_dispatcher(false);
+
+    // Subscribers of the gameplay events
+    let mut game_event_dispatcher = ::specs::DispatcherBuilder::new()
+        .add(::system::EventSoundSystem, "event_sound", &[])
+        .add(::system::StatsSystem, "stats", &[])
+        .add(::system::AchievementSystem, "achievement", &[])
         .build();
 
     let mut prepare_game_draw_dispatcher = ::specs::DispatcherBuilder::new()
@@ -269,6 +366,11 @@ fn new_game() -> ControlFlow {
     let mut fps_counter = fps_counter::FPSCounter::new();
     let mut benchmarker = util::Benchmarker::new();
     let mut last_update_instant = Instant::now();
+    let mut update_accumulator = 0.0;
+    let update_step_duration = Duration::new(
+        0,
+        (::CONFIG.update_step_time * 1_000_000_000.0) as u32,
+    );
 
     loop {
         benchmarker.start("pre_update");
@@ -278,10 +380,9 @@ fn new_game() -> ControlFlow {
 
         // Poll events
         {
+            // Events are cleared once consumed by an update
             let mut events = world.write_resource::<::resource::Events>();
 
-            events.0.clear();
-
             let mut done = false;
 
             events_loop.poll_events(|ev| {
@@ -367,16 +468,39 @@ fn new_game() -> ControlFlow {
         if world.read_resource::<::resource::MenuState>().paused() {
             world.write_resource::<::resource::UpdateTime>().0 = 0.0;
             pause_update_dispatcher.dispatch(&mut world.res);
+            world.write_resource::<::resource::Events>().0.clear();
         } else {
-            world.write_resource::<::resource::GameDuration>().0 += delta_time;
-            world.write_resource::<::resource::UpdateTime>().0 = delta_time
+            let real_update_time = delta_time
                 .as_secs()
                 .saturating_mul(1_000_000_000)
                 .saturating_add(delta_time.subsec_nanos() as u64)
                 as f32 / 1_000_000_000.0;
-            game_update_dispatcher.dispatch(&mut world.res);
-            world.maintain();
-            game_system.run(&mut world);
+            update_accumulator = (update_accumulator + real_update_time)
+                .min(::CONFIG.update_step_time * ::CONFIG.max_update_steps as f32);
+
+            // Run the game at a fixed step so the same seed and inputs give the same result
+            while update_accumulator >= ::CONFIG.update_step_time
+                && !world.read_resource::<::resource::MenuState>().paused()
+            {
+                update_accumulator -= ::CONFIG.update_step_time;
+                world.write_resource::<::resource::GameDuration>().0 += update_step_duration;
+                let update_time = {
+                    let slow_motion = world.read_resource::<::resource::PowerUps>().slow_motion > 0.0;
+                    let game_speed = world.read_resource::<::resource::Replays>().game_speed()
+                        .unwrap_or_else(|| world.read_resource::<::resource::Save>().game_speed());
+                    world.write_resource::<::resource::TimeScale>().update(::CONFIG.update_step_time, game_speed, slow_motion)
+                };
+                world.write_resource::<::resource::UpdateTime>().0 = update_time;
+                game_update_dispatcher.dispatch(&mut world.res);
+                world.maintain();
+                // Events emitted by the game system are read at the next update
+                game_event_dispatcher.dispatch(&mut world.res);
+                world.write_resource::<::resource::GameEvents>().0.clear();
+                game_system.run(&mut world);
+                world.write_resource::<::resource::Events>().0.clear();
+            }
+            world.write_resource::<::resource::Interpolation>().alpha =
+                update_accumulator / ::CONFIG.update_step_time;
             prepare_game_draw_dispatcher.dispatch(&mut world.res);
             world.maintain();
         }
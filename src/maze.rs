@@ -1,11 +1,13 @@
 use rand::distributions::{IndependentSample, Range};
-use std::collections::HashSet;
-use std::collections::HashMap;
+use util::{FixedHashMap, FixedHashSet};
 use std::hash::Hash;
 use util::Pop;
 use std::ops::Mul;
 use typenum;
 
+/// Cost added to go through a hazard cell, a cell costs 10
+const HAZARD_COST: isize = 200;
+
 pub struct Opening<D>
 where
     D: ::na::Dim + ::na::DimName,
@@ -23,7 +25,11 @@ where
     D::Value: Mul<typenum::UInt<typenum::UTerm, typenum::B1>, Output = D::Value>
         + ::generic_array::ArrayLength<isize>,
 {
-    pub walls: HashSet<::na::VectorN<isize, D>>,
+    pub walls: FixedHashSet<::na::VectorN<isize, D>>,
+    /// Closed doors, they block paths like walls
+    pub doors: FixedHashSet<::na::VectorN<isize, D>>,
+    /// Cells that kill the player, paths avoid them if possible
+    pub hazards: FixedHashSet<::na::VectorN<isize, D>>,
     pub size: ::na::VectorN<isize, D>,
     pub openings: Vec<Opening<D>>,
     pub neighbours: Vec<::na::VectorN<isize, D>>,
@@ -51,6 +57,10 @@ where
             .any(|n| self.walls.contains(&n))
     }
 
+    pub fn is_blocked(&self, cell: &::na::VectorN<isize, D>) -> bool {
+        self.walls.contains(cell) || self.doors.contains(cell)
+    }
+
     pub fn is_corridor(&self, cell: &::na::VectorN<isize, D>) -> bool {
         !self.walls.contains(cell)
             && self.openings
@@ -67,7 +77,9 @@ where
     pub fn new_empty(scale: f32) -> Self {
         Maze {
             scale,
-            walls: HashSet::new(),
+            walls: FixedHashSet::default(),
+            doors: FixedHashSet::default(),
+            hazards: FixedHashSet::default(),
             size: ::na::zero(),
             openings: Self::openings(),
             neighbours: Self::neighbours(),
@@ -77,7 +89,9 @@ where
     pub fn new_rectangle(size: ::na::VectorN<isize, D>, scale: f32) -> Self {
         Maze {
             scale,
-            walls: HashSet::new(),
+            walls: FixedHashSet::default(),
+            doors: FixedHashSet::default(),
+            hazards: FixedHashSet::default(),
             size: size,
             openings: Self::openings(),
             neighbours: Self::neighbours(),
@@ -109,7 +123,7 @@ where
             assert!(s >= size * 2);
         }
         let dl = size * ::na::VectorN::<isize, D>::from_iterator((1..2).cycle());
-        let mut new_walls = HashSet::new();
+        let mut new_walls = FixedHashSet::default();
         for wall in self.walls.iter() {
             if wall >= &dl && wall < &(self.size.clone() - dl.clone()) {
                 new_walls.insert(wall - dl.clone());
@@ -122,7 +136,7 @@ where
     /// Extend the maze with empty cell
     pub fn extend(&mut self, size: isize) {
         let dl = size * ::na::VectorN::<isize, D>::from_iterator((1..2).cycle());
-        let mut new_walls = HashSet::new();
+        let mut new_walls = FixedHashSet::default();
         for wall in self.walls.iter() {
             new_walls.insert(wall + dl.clone());
         }
@@ -167,20 +181,20 @@ where
     }
 
     /// Filter(openings) -> if we keep the cell
-    pub fn compute_zones<F>(&self, filter: F) -> Vec<HashSet<::na::VectorN<isize, D>>>
+    pub fn compute_zones<F>(&self, filter: F) -> Vec<FixedHashSet<::na::VectorN<isize, D>>>
     where
         F: Fn(&Self, &::na::VectorN<isize, D>) -> bool,
     {
-        let mut unvisited = HashSet::new();
+        let mut unvisited = FixedHashSet::default();
         for cell in self.iterate_maze() {
             unvisited.insert(cell);
         }
 
-        let mut to_visit = HashSet::new();
+        let mut to_visit = FixedHashSet::default();
         let mut zones = Vec::new();
 
         while let Some(cell) = unvisited.pop() {
-            let mut zone = HashSet::new();
+            let mut zone = FixedHashSet::default();
             to_visit.insert(cell);
 
             while let Some(cell) = to_visit.pop() {
@@ -206,7 +220,7 @@ where
     }
 
     /// room zones without neighbouring corridors
-    pub fn compute_inner_room_zones(&self) -> Vec<HashSet<::na::VectorN<isize, D>>> {
+    pub fn compute_inner_room_zones(&self) -> Vec<FixedHashSet<::na::VectorN<isize, D>>> {
         let mut rooms = self.compute_room_zones();
         for room in &mut rooms {
             room.retain(|cell| !self.is_neighbouring_corridor(cell));
@@ -214,7 +228,7 @@ where
         rooms
     }
 
-    pub fn compute_room_zones(&self) -> Vec<HashSet<::na::VectorN<isize, D>>> {
+    pub fn compute_room_zones(&self) -> Vec<FixedHashSet<::na::VectorN<isize, D>>> {
         self.compute_zones(|maze, cell| {
             !maze.walls.contains(cell)
                 && maze.openings
@@ -229,10 +243,10 @@ where
         })
     }
 
-    pub fn compute_dead_room_zones(&self) -> Vec<HashSet<::na::VectorN<isize, D>>> {
+    pub fn compute_dead_room_zones(&self) -> Vec<FixedHashSet<::na::VectorN<isize, D>>> {
         let mut rooms = self.compute_room_zones();
         rooms.retain(|room| {
-            let superset = room.iter().fold(HashSet::new(), |mut acc, cell| {
+            let superset = room.iter().fold(FixedHashSet::default(), |mut acc, cell| {
                 self.neighbours
                     .iter()
                     .map(|n| n + cell)
@@ -247,17 +261,17 @@ where
         rooms
     }
 
-    pub fn compute_corridor_zones(&self) -> Vec<HashSet<::na::VectorN<isize, D>>> {
+    pub fn compute_corridor_zones(&self) -> Vec<FixedHashSet<::na::VectorN<isize, D>>> {
         self.compute_zones(|maze, cell| maze.is_corridor(cell))
     }
 
     /// Return all dead room with its entry corridor
-    pub fn compute_dead_room_and_corridor_zones(&self) -> Vec<HashSet<::na::VectorN<isize, D>>> {
+    pub fn compute_dead_room_and_corridor_zones(&self) -> Vec<FixedHashSet<::na::VectorN<isize, D>>> {
         let mut rooms = self.compute_dead_room_zones();
         let corridors = self.compute_corridor_zones();
         for room in &mut rooms {
             let opening = {
-                let superset = room.iter().fold(HashSet::new(), |mut acc, cell| {
+                let superset = room.iter().fold(FixedHashSet::default(), |mut acc, cell| {
                     self.neighbours
                         .iter()
                         .map(|n| n + cell)
@@ -281,6 +295,96 @@ where
         rooms
     }
 
+    /// Return a closed walk that goes through all cells of the zone connected to start
+    ///
+    /// The walk is a depth first tour: each cell is visited when entered and when
+    /// going back to it, so the last cell is a neighbour of the first one.
+    pub fn zone_loop(
+        &self,
+        zone: &FixedHashSet<::na::VectorN<isize, D>>,
+        start: ::na::VectorN<isize, D>,
+    ) -> Vec<::na::VectorN<isize, D>> {
+        let mut res = vec![start.clone()];
+        let mut visited = FixedHashSet::default();
+        visited.insert(start.clone());
+        let mut stack = vec![start];
+
+        while let Some(cell) = stack.last().cloned() {
+            let next = self.neighbours
+                .iter()
+                .map(|n| n + cell.clone())
+                .find(|n| zone.contains(n) && !visited.contains(n));
+
+            if let Some(next) = next {
+                visited.insert(next.clone());
+                res.push(next.clone());
+                stack.push(next);
+            } else {
+                stack.pop();
+                if let Some(parent) = stack.last() {
+                    res.push(parent.clone());
+                }
+            }
+        }
+
+        // The tour ends on start
+        if res.len() > 1 {
+            res.pop();
+        }
+        res
+    }
+
+    /// Cells reachable from start through openings, closed doors block the way
+    pub fn reachable_cells(
+        &self,
+        start: ::na::VectorN<isize, D>,
+    ) -> FixedHashSet<::na::VectorN<isize, D>> {
+        let mut reachable = FixedHashSet::default();
+        let mut to_visit = vec![start];
+        while let Some(cell) = to_visit.pop() {
+            let outside = (0..D::dim()).any(|i| cell[i] < 0 || cell[i] >= self.size[i]);
+            if outside || self.is_blocked(&cell) || !reachable.insert(cell.clone()) {
+                continue;
+            }
+            for opening in self.openings.iter() {
+                if opening
+                    .requires
+                    .iter()
+                    .all(|o| !self.is_blocked(&(o + cell.clone())))
+                {
+                    to_visit.push(opening.cell.clone() + cell.clone());
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Corridor cells of the path from start to goal that disconnect goal from start
+    /// when closed, in path order
+    pub fn chokepoints(
+        &mut self,
+        start: ::na::VectorN<isize, D>,
+        goal: ::na::VectorN<isize, D>,
+    ) -> Vec<::na::VectorN<isize, D>> {
+        let path = match self.inner_find_path(start.clone(), goal.clone()) {
+            Some(path) => path,
+            None => return vec![],
+        };
+
+        let mut res = vec![];
+        for cell in path {
+            if cell == start || cell == goal || !self.is_corridor(&cell) {
+                continue;
+            }
+            self.doors.insert(cell.clone());
+            if self.inner_find_path(start.clone(), goal.clone()).is_none() {
+                res.push(cell.clone());
+            }
+            self.doors.remove(&cell);
+        }
+        res
+    }
+
     fn is_on_border(&self, v: &::na::VectorN<isize, D>) -> bool {
         let one = ::na::VectorN::<isize, D>::from_iterator((1..2).cycle());
         !(v >= &one && v + one < self.size)
@@ -298,7 +402,6 @@ where
         F: Fn(&::na::VectorN<isize, D>) -> bool,
     {
         let mut res = vec![];
-        let mut rng = ::rand::thread_rng();
         let mut candidates = self.iterate_maze();
         candidates.retain(|cell| filter(cell));
 
@@ -313,7 +416,7 @@ where
             if candidates.is_empty() {
                 return res;
             }
-            let choosen = Range::new(0, candidates.len()).ind_sample(&mut rng);
+            let choosen = Range::new(0, candidates.len()).ind_sample(&mut *::util::rng());
             let cell = candidates.swap_remove(choosen);
             self.walls.remove(&cell);
             let opening = self.neighbours
@@ -418,6 +521,11 @@ where
             .map(|path| path.iter().map(|cell| self.to_world(cell)).collect())
     }
 
+    pub fn open_door(&mut self, pos: &::na::Vector3<f32>) {
+        let cell = self.to_inner(pos);
+        self.doors.remove(&cell);
+    }
+
     #[inline]
     pub fn to_inner(&self, coords: &::na::Vector3<f32>) -> ::na::VectorN<isize, D> {
         ::na::VectorN::<isize, D>::from_iterator(coords.iter().map(|&c| (c / self.scale) as isize))
@@ -445,9 +553,15 @@ where
                     if opening
                         .requires
                         .iter()
-                        .all(|o| !self.walls.contains(&(o + cell.clone())))
+                        .all(|o| !self.is_blocked(&(o + cell.clone())))
                     {
-                        res.push((opening.cell.clone() + cell, opening.cost));
+                        let next = opening.cell.clone() + cell;
+                        let cost = if self.hazards.contains(&next) {
+                            opening.cost + HAZARD_COST
+                        } else {
+                            opening.cost
+                        };
+                        res.push((next, cost));
                     }
                 }
                 res
@@ -480,7 +594,7 @@ where
             assert_eq!(size.wrapping_rem(2), 1);
         }
 
-        let mut grid = HashMap::new();
+        let mut grid = FixedHashMap::default();
 
         for (i, cell) in Self::iterate_area(&size).iter().enumerate() {
             grid.insert(
@@ -577,15 +691,13 @@ where
             _ => unimplemented!(),
         }
 
-        let mut rng = ::rand::thread_rng();
-
         let stop = ((walls.len() as f64) * (1. - percent / 100.)) as usize;
 
         while walls.len() > stop {
-            let i = ::rand::distributions::Range::new(0, walls.len()).ind_sample(&mut rng);
+            let i = ::rand::distributions::Range::new(0, walls.len()).ind_sample(&mut *::util::rng());
             let wall = walls.swap_remove(i);
 
-            let mut groups = HashSet::new();
+            let mut groups = FixedHashSet::default();
             for cell in &wall {
                 groups.insert(grid[cell].group);
             }
@@ -603,7 +715,7 @@ where
             }
         }
 
-        let mut walls = HashSet::new();
+        let mut walls = FixedHashSet::default();
         for (key, value) in grid {
             if value.wall {
                 walls.insert(key);
@@ -613,6 +725,8 @@ where
         Maze {
             size,
             walls,
+            doors: FixedHashSet::default(),
+            hazards: FixedHashSet::default(),
             neighbours: Self::neighbours(),
             openings: Self::openings(),
             scale,
@@ -623,13 +737,12 @@ where
         assert!(self.size.iter().all(|&s| s != 0));
 
         let ranges: Vec<_> = self.size.iter().map(|&s| Range::new(0, s)).collect();
-        let mut rng = ::rand::thread_rng();
 
         let mut vec =
-            ::na::VectorN::<isize, D>::from_iterator(ranges.iter().map(|r| r.ind_sample(&mut rng)));
+            ::na::VectorN::<isize, D>::from_iterator(ranges.iter().map(|r| r.ind_sample(&mut *::util::rng())));
         while self.walls.contains(&vec) {
             vec = ::na::VectorN::<isize, D>::from_iterator(
-                ranges.iter().map(|r| r.ind_sample(&mut rng)),
+                ranges.iter().map(|r| r.ind_sample(&mut *::util::rng())),
             );
         }
         vec
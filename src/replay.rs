@@ -0,0 +1,252 @@
+use show_message::UnwrapOrShow;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use resource::Input;
+use util::Direction;
+
+/// Inputs of the player during one game update
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ReplayTick {
+    /// Inputs pressed (true) or released (false)
+    #[serde(default)]
+    pub inputs: Vec<(Input, bool)>,
+    /// Mouse motions already scaled by the mouse sensibility
+    #[serde(default)]
+    pub motions: Vec<(f32, f32)>,
+    /// Position of the player at the start of the update, used to draw ghosts
+    pub position: [f32; 3],
+}
+
+/// One part of a level played from its creation to the teleport
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReplayPart {
+    pub seed: u32,
+    /// Directions held when the part was created
+    pub directions: Vec<Direction>,
+    pub ticks: Vec<ReplayTick>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Replay {
+    pub level: usize,
+    pub game_speed: f32,
+    pub duration: Duration,
+    pub parts: Vec<ReplayPart>,
+}
+
+impl Replay {
+    fn path(dir: &Path, level: usize) -> PathBuf {
+        dir.join(format!("level_{}.ron", level))
+    }
+
+    /// Load the best run of the level if any
+    pub fn load(dir: &Path, level: usize) -> Option<Self> {
+        File::open(Self::path(dir, level)).ok()
+            .and_then(|file| ::ron::de::from_reader(file).ok())
+    }
+
+    pub fn save(&self, dir: &Path) {
+        self.write(dir, &Self::path(dir, self.level));
+    }
+
+    fn write(&self, dir: &Path, path: &Path) {
+        fs::create_dir_all(dir)
+            .unwrap_or_else_show(|e| format!("Failed to create replays directory at {}: {}", dir.display(), e));
+        let string = ::ron::ser::to_string(&self).unwrap();
+        let mut file = File::create(&path)
+            .unwrap_or_else_show(|e| format!("Failed to create replay file at {}: {}", path.display(), e));
+        file.write_all(string.as_bytes())
+            .unwrap_or_else_show(|e| format!("Failed to write to replay file {}: {}", path.display(), e));
+    }
+}
+
+struct Playback {
+    replay: Replay,
+    part: usize,
+    tick: usize,
+}
+
+impl Playback {
+    fn new(replay: Replay) -> Self {
+        Playback {
+            replay,
+            part: 0,
+            tick: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<&ReplayTick> {
+        let tick = self.replay.parts.get(self.part).and_then(|part| part.ticks.get(self.tick));
+        if tick.is_some() {
+            self.tick += 1;
+        }
+        tick
+    }
+}
+
+/// Record the current run, play a run back and follow the best run as a ghost
+pub struct Replays {
+    /// Directory of the replays of the profile
+    path: PathBuf,
+    available: HashSet<usize>,
+    recording: Option<Replay>,
+    playing: Option<Playback>,
+    ghost: Option<Playback>,
+}
+
+impl Replays {
+    pub fn new(profile: &str) -> Self {
+        let path = ::resource::profile_path(profile).join("replays");
+        let available = (0..::CONFIG.levels.len())
+            .filter(|&level| Replay::path(&path, level).exists())
+            .collect();
+
+        Replays {
+            path,
+            available,
+            recording: None,
+            playing: None,
+            ghost: None,
+        }
+    }
+
+    /// Play the replay back without recording nor ghost
+    pub fn from_replay(replay: Replay) -> Self {
+        Replays {
+            path: PathBuf::new(),
+            available: HashSet::new(),
+            recording: None,
+            playing: Some(Playback::new(replay)),
+            ghost: None,
+        }
+    }
+
+    /// Number of updates recorded in the part played back
+    pub fn part_ticks(&self, part: usize) -> usize {
+        self.playing.as_ref()
+            .and_then(|playback| playback.replay.parts.get(part))
+            .map(|part| part.ticks.len())
+            .unwrap_or(0)
+    }
+
+    /// Whether the level has a best run saved
+    pub fn available(&self, level: usize) -> bool {
+        self.available.contains(&level)
+    }
+
+    /// Record a new run of the level, the best run is followed as a ghost
+    pub fn start_run(&mut self, level: usize, game_speed: f32) {
+        self.stop();
+        self.recording = Some(Replay {
+            level,
+            game_speed,
+            duration: Duration::new(0, 0),
+            parts: vec![],
+        });
+        self.ghost = Replay::load(&self.path, level).map(Playback::new);
+    }
+
+    /// Play the best run of the level back, return false if there is none
+    pub fn start_playback(&mut self, level: usize) -> bool {
+        self.stop();
+        self.playing = Replay::load(&self.path, level).map(Playback::new);
+        self.playing.is_some()
+    }
+
+    pub fn stop(&mut self) {
+        self.recording = None;
+        self.playing = None;
+        self.ghost = None;
+    }
+
+    pub fn playing(&self) -> bool {
+        self.playing.is_some()
+    }
+
+    pub fn has_ghost(&self) -> bool {
+        self.ghost.is_some()
+    }
+
+    /// Game speed of the run recorded or played back
+    pub fn game_speed(&self) -> Option<f32> {
+        self.playing.as_ref().map(|playback| &playback.replay)
+            .or(self.recording.as_ref())
+            .map(|replay| replay.game_speed)
+    }
+
+    /// Start or restart a part of the level and return the seed of its random generator
+    pub fn start_part(&mut self, part: usize, directions: &mut Vec<Direction>) -> u32 {
+        if let Some(ref mut ghost) = self.ghost {
+            ghost.part = part;
+            ghost.tick = 0;
+        }
+
+        if let Some(ref mut playback) = self.playing {
+            playback.part = part;
+            playback.tick = 0;
+            if let Some(replay_part) = playback.replay.parts.get(part) {
+                *directions = replay_part.directions.clone();
+                return replay_part.seed;
+            }
+        }
+
+        let seed = ::rand::random();
+        if let Some(ref mut recording) = self.recording {
+            recording.parts.truncate(part);
+            recording.parts.push(ReplayPart {
+                seed,
+                directions: directions.clone(),
+                ticks: vec![],
+            });
+        }
+        seed
+    }
+
+    /// Record the inputs of the update or replace them by the played back ones
+    pub fn tick(&mut self, tick: ReplayTick) -> ReplayTick {
+        if let Some(ref mut playback) = self.playing {
+            return playback.next().cloned().unwrap_or_default();
+        }
+        if let Some(part) = self.recording.as_mut().and_then(|recording| recording.parts.last_mut()) {
+            part.ticks.push(tick.clone());
+        }
+        tick
+    }
+
+    /// Position of the ghost for this update
+    pub fn ghost_position(&mut self) -> Option<::na::Vector3<f32>> {
+        self.ghost.as_mut()
+            .and_then(|ghost| ghost.next())
+            .map(|tick| ::na::Vector3::new(tick.position[0], tick.position[1], tick.position[2]))
+    }
+
+    /// End the run and return its replay if it was recorded
+    pub fn finish(&mut self, duration: Duration) -> Option<Replay> {
+        let replay = self.recording.take().map(|mut replay| {
+            replay.duration = duration;
+            replay
+        });
+        self.stop();
+        replay
+    }
+
+    /// Save the replay as the best run of its level
+    pub fn save_best(&mut self, replay: Replay) {
+        replay.save(&self.path);
+        self.available.insert(replay.level);
+    }
+
+    /// Keep a run that failed verification to investigate it, return the path of its file
+    pub fn save_rejected(&self, replay: &Replay) -> PathBuf {
+        let dir = self.path.join("rejected");
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let path = dir.join(format!("level_{}_{}.ron", replay.level, timestamp));
+        replay.write(&dir, &path);
+        path
+    }
+}
@@ -8,15 +8,20 @@ pub use graphics::Graphics;
 use std::io::Write;
 use std::sync::Arc;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::collections::HashSet;
 use std::fs;
 use std::ffi::OsStr;
 use std::io::Read;
 use std::io::Cursor;
 use util::Direction;
 pub use audio::Audio;
+pub use replay::Replays;
+pub use stats::Stats;
+pub use achievement::Achievements;
+pub use verify::Verifier;
 use show_message::UnwrapOrShow;
 use winit::MouseButton;
 
@@ -31,36 +36,167 @@ pub type ImGuiOption = Option<::imgui::ImGui>;
 
 pub struct FpsCounter(pub usize);
 
+/// Version of the save format, increased when a migration is added
+const SAVE_VERSION: u32 = 1;
+
+/// Migrations of the save, the nth one migrates from version n to version n+1
+const SAVE_MIGRATIONS: [fn(&mut Save); SAVE_VERSION as usize] = [
+    migrate_save_0_to_1,
+];
+
+/// Version 0 saves have no version field, missing fields are filled with their default values
+///
+/// Their times were recorded before runs were verified.
+fn migrate_save_0_to_1(save: &mut Save) {
+    for score in save.scores.values_mut() {
+        score.unverified = score.bests.iter()
+            .chain(score.lasts.iter())
+            .cloned()
+            .collect();
+        score.unverified.sort();
+        score.unverified.dedup();
+    }
+}
+
+/// Fields missing in the save file are filled with their default values
 #[derive(Deserialize, Serialize)]
+#[serde(default)]
 pub struct Save {
+    /// Missing in saves older than the version field
+    #[serde(default)]
+    version: u32,
+    /// Corrupt save file moved away when loading
+    #[serde(skip)]
+    quarantined: Option<PathBuf>,
+    /// Copy of a save file written by a newer version, its unknown fields are lost on next save
+    #[serde(skip)]
+    newer_backup: Option<PathBuf>,
+    #[serde(skip)]
+    profile: String,
     mouse_sensibility: f32,
     scores: HashMap<usize, Score>,
     input_settings: InputSettings,
     fullscreen: bool,
     vulkan_device_uuid: Option<[u8; 16]>,
     field_of_view: f32,
+    #[serde(default = "default_game_speed")]
+    game_speed: f32,
     effect_volume: f32,
     music_volume: f32,
     custom_level_conf: CustomLevelConf,
+    /// Statistics of the last attempts, most recent first
+    stats: Vec<::stats::RunStats>,
+    achievements: ::achievement::AchievementProgress,
+    imported_scores: BTreeMap<String, ImportedScores>,
+}
+
+/// Scores and statistics imported from an export of another machine
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ImportedScores {
+    pub scores: HashMap<usize, Score>,
+    /// Most recent first
+    pub stats: Vec<::stats::RunStats>,
+}
+
+/// Put the newer runs not already recorded first and drop the oldest ones beyond the history,
+/// return the number of newer runs kept
+fn merge_runs(runs: &mut Vec<::stats::RunStats>, newer: Vec<::stats::RunStats>) -> usize {
+    let mut merged = newer.into_iter()
+        .filter(|run| !runs.contains(run))
+        .collect::<Vec<_>>();
+    let kept = merged.len().min(::CONFIG.stats_history);
+    merged.extend(runs.drain(..));
+    merged.truncate(::CONFIG.stats_history);
+    *runs = merged;
+    kept
+}
+
+fn default_game_speed() -> f32 {
+    1.0
 }
 
 #[derive(Deserialize, Serialize)]
+#[serde(default)]
 pub struct InputSettings {
     shoot: PossibleInput,
     forward: PossibleInput,
     backward: PossibleInput,
     left: PossibleInput,
     right: PossibleInput,
+    #[serde(default = "default_hook_input")]
+    hook: PossibleInput,
+    #[serde(default = "default_reel_in_input")]
+    reel_in: PossibleInput,
+    #[serde(default = "default_reel_out_input")]
+    reel_out: PossibleInput,
+    #[serde(default = "default_up_input")]
+    up: PossibleInput,
+    #[serde(default = "default_down_input")]
+    down: PossibleInput,
+    #[serde(default = "default_roll_left_input")]
+    roll_left: PossibleInput,
+    #[serde(default = "default_roll_right_input")]
+    roll_right: PossibleInput,
+    #[serde(default = "default_dash_input")]
+    dash: PossibleInput,
+    #[serde(default = "default_jump_input")]
+    jump: PossibleInput,
 }
 
-impl InputSettings {
-    pub fn default() -> Self {
+fn default_hook_input() -> PossibleInput {
+    PossibleInput::MouseButton(::winit::MouseButton::Right)
+}
+
+fn default_reel_in_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::E)
+}
+
+fn default_reel_out_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::Q)
+}
+
+fn default_up_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::R)
+}
+
+fn default_down_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::F)
+}
+
+fn default_roll_left_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::Z)
+}
+
+fn default_roll_right_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::C)
+}
+
+fn default_dash_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::LShift)
+}
+
+fn default_jump_input() -> PossibleInput {
+    PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::Space)
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
         InputSettings {
             shoot: PossibleInput::MouseButton(::winit::MouseButton::Left),
             forward: PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::W),
             backward: PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::S),
             left: PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::A),
             right: PossibleInput::VirtualKeyCode(::winit::VirtualKeyCode::D),
+            hook: default_hook_input(),
+            reel_in: default_reel_in_input(),
+            reel_out: default_reel_out_input(),
+            up: default_up_input(),
+            down: default_down_input(),
+            roll_left: default_roll_left_input(),
+            roll_right: default_roll_right_input(),
+            dash: default_dash_input(),
+            jump: default_jump_input(),
         }
     }
 }
@@ -95,16 +231,26 @@ impl PossibleInput {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum Input {
     Shoot,
     Direction(Direction),
+    Hook,
+    ReelIn,
+    ReelOut,
+    RollLeft,
+    RollRight,
+    Dash,
+    Jump,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
 pub struct Score {
     pub bests: Vec<Duration>,
     pub lasts: Vec<Duration>,
+    /// Times whose run hasn't passed the verification
+    pub unverified: Vec<Duration>,
 }
 
 impl Score {
@@ -112,8 +258,14 @@ impl Score {
         Score {
             bests: vec![],
             lasts: vec![],
+            unverified: vec![],
         }
     }
+
+    pub fn verified(&self, duration: Duration) -> bool {
+        !self.unverified.contains(&duration)
+    }
+
     fn insert(&mut self, duration: Duration) {
         self.bests.push(duration);
         self.bests.sort();
@@ -121,18 +273,122 @@ impl Score {
 
         self.lasts.insert(0, duration);
         self.lasts.truncate(10);
+        self.drop_unverified();
+    }
+
+    /// Add the durations not already recorded, the local last ones stay first
+    fn merge(&mut self, other: Score) {
+        for duration in other.unverified {
+            if !self.unverified.contains(&duration) && !self.bests.contains(&duration) && !self.lasts.contains(&duration) {
+                self.unverified.push(duration);
+            }
+        }
+        for duration in other.bests {
+            if !self.bests.contains(&duration) {
+                self.bests.push(duration);
+            }
+        }
+        self.bests.sort();
+        self.bests.truncate(10);
+
+        for duration in other.lasts {
+            if !self.lasts.contains(&duration) {
+                self.lasts.push(duration);
+            }
+        }
+        self.lasts.truncate(10);
+        self.drop_unverified();
+    }
+
+    /// Forget the unverified durations no longer recorded
+    fn drop_unverified(&mut self) {
+        let (bests, lasts) = (&self.bests, &self.lasts);
+        self.unverified.retain(|duration| bests.contains(duration) || lasts.contains(duration));
     }
 }
 
-const APP_INFO: AppInfo = AppInfo { name: "HyperZen Training", author: "thiolliere" };
+pub const APP_INFO: AppInfo = AppInfo { name: "HyperZen Training", author: "thiolliere" };
 const FILENAME: &str = "save.ron";
+/// Profile stored at the root of the application directory like saves before profiles
+pub const DEFAULT_PROFILE: &str = "default";
 
 lazy_static! {
-    static ref SAVE_PATH: PathBuf = {
-        let mut path = app_root(AppDataType::UserConfig, &APP_INFO).unwrap();
-        path.push(FILENAME);
-        path
-    };
+    static ref APP_PATH: PathBuf = app_root(AppDataType::UserConfig, &APP_INFO).unwrap();
+    static ref PROFILES_PATH: PathBuf = APP_PATH.join("profiles");
+    static ref CURRENT_PROFILE_PATH: PathBuf = APP_PATH.join("profile.ron");
+}
+
+/// Directory of the save and replays of the profile
+pub fn profile_path(profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        APP_PATH.clone()
+    } else {
+        PROFILES_PATH.join(profile)
+    }
+}
+
+/// Profile names are used as directory names
+pub fn valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 32
+        && name != DEFAULT_PROFILE
+        && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == ' ')
+}
+
+/// Names of the existing profiles, the default one first
+pub fn profiles() -> Vec<String> {
+    let mut profiles = fs::read_dir(PROFILES_PATH.as_path())
+        .map(|entries| {
+            entries.filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| valid_profile_name(name))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or(vec![]);
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    profiles
+}
+
+/// Profile played last, the default one if it doesn't exist anymore
+pub fn current_profile() -> String {
+    File::open(CURRENT_PROFILE_PATH.as_path()).ok()
+        .and_then(|file| ::ron::de::from_reader::<_, String>(file).ok())
+        .and_then(|profile| if profiles().contains(&profile) { Some(profile) } else { None })
+        .unwrap_or(DEFAULT_PROFILE.to_string())
+}
+
+pub fn set_current_profile(profile: &str) {
+    let string = ::ron::ser::to_string(&profile).unwrap();
+    let mut file = File::create(CURRENT_PROFILE_PATH.as_path())
+        .unwrap_or_else_show(|e| format!("Failed to create profile file at {}: {}", CURRENT_PROFILE_PATH.display(), e));
+    file.write_all(string.as_bytes())
+        .unwrap_or_else_show(|e| format!("Failed to write to profile file {}: {}", CURRENT_PROFILE_PATH.display(), e));
+}
+
+impl Default for Save {
+    fn default() -> Self {
+        Save {
+            version: SAVE_VERSION,
+            quarantined: None,
+            newer_backup: None,
+            profile: DEFAULT_PROFILE.to_string(),
+            mouse_sensibility: ::CONFIG.mouse_sensibility,
+            scores: HashMap::new(),
+            input_settings: InputSettings::default(),
+            fullscreen: true,
+            vulkan_device_uuid: None,
+            effect_volume: 1.0,
+            music_volume: 1.0,
+            field_of_view: ::CONFIG.field_of_view,
+            game_speed: 1.0,
+            custom_level_conf: CustomLevelConf::default(),
+            stats: vec![],
+            achievements: ::achievement::AchievementProgress::default(),
+            imported_scores: BTreeMap::new(),
+        }
+    }
 }
 
 impl Save {
@@ -143,6 +399,15 @@ impl Save {
             Input::Direction(Direction::Backward) => self.input_settings.backward = set,
             Input::Direction(Direction::Left) => self.input_settings.left = set,
             Input::Direction(Direction::Right) => self.input_settings.right = set,
+            Input::Hook => self.input_settings.hook = set,
+            Input::ReelIn => self.input_settings.reel_in = set,
+            Input::ReelOut => self.input_settings.reel_out = set,
+            Input::Direction(Direction::Up) => self.input_settings.up = set,
+            Input::Direction(Direction::Down) => self.input_settings.down = set,
+            Input::RollLeft => self.input_settings.roll_left = set,
+            Input::RollRight => self.input_settings.roll_right = set,
+            Input::Dash => self.input_settings.dash = set,
+            Input::Jump => self.input_settings.jump = set,
         }
         self.save();
     }
@@ -154,23 +419,136 @@ impl Save {
             Input::Direction(Direction::Backward) => self.input_settings.backward.clone(),
             Input::Direction(Direction::Left) => self.input_settings.left.clone(),
             Input::Direction(Direction::Right) => self.input_settings.right.clone(),
+            Input::Hook => self.input_settings.hook.clone(),
+            Input::ReelIn => self.input_settings.reel_in.clone(),
+            Input::ReelOut => self.input_settings.reel_out.clone(),
+            Input::Direction(Direction::Up) => self.input_settings.up.clone(),
+            Input::Direction(Direction::Down) => self.input_settings.down.clone(),
+            Input::RollLeft => self.input_settings.roll_left.clone(),
+            Input::RollRight => self.input_settings.roll_right.clone(),
+            Input::Dash => self.input_settings.dash.clone(),
+            Input::Jump => self.input_settings.jump.clone(),
         }
     }
 
+    /// Load the save of the current profile, a corrupt save is moved away and replaced by a
+    /// default one
     pub fn new() -> Self {
-        File::open(SAVE_PATH.as_path()).ok()
-            .and_then(|file| ::ron::de::from_reader(file).ok())
-            .unwrap_or(Save {
-                mouse_sensibility: ::CONFIG.mouse_sensibility,
-                scores: HashMap::new(),
-                input_settings: InputSettings::default(),
-                fullscreen: true,
-                vulkan_device_uuid: None,
-                effect_volume: 1.0,
-                music_volume: 1.0,
-                field_of_view: ::CONFIG.field_of_view,
-                custom_level_conf: CustomLevelConf::default(),
-            })
+        let profile = current_profile();
+        let path = profile_path(&profile).join(FILENAME);
+        let mut save = if path.exists() {
+            match Save::read(&path) {
+                Ok(mut save) => {
+                    if save.version > SAVE_VERSION {
+                        println!("Save file {} has version {} newer than {}", path.display(), save.version, SAVE_VERSION);
+                        save.newer_backup = Some(Save::backup_newer(&path, save.version));
+                        save.version = SAVE_VERSION;
+                    }
+                    save
+                },
+                Err(e) => {
+                    println!("Failed to load save file {}: {}", path.display(), e);
+                    let mut save = Save::default();
+                    save.quarantined = Some(Save::quarantine(&path));
+                    save
+                },
+            }
+        } else {
+            Save::default()
+        };
+        save.profile = profile;
+        save
+    }
+
+    /// Read a save file and migrate it to the current version
+    fn read(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        Save::parse(file)
+    }
+
+    /// Saves newer than the current version are left as they are
+    fn parse<R: Read>(reader: R) -> Result<Self, String> {
+        let mut save: Save = ::ron::de::from_reader(reader).map_err(|e| e.to_string())?;
+        save.migrate();
+        Ok(save)
+    }
+
+    fn migrate(&mut self) {
+        while self.version < SAVE_VERSION {
+            SAVE_MIGRATIONS[self.version as usize](self);
+            self.version += 1;
+        }
+    }
+
+    /// Copy the save file written by a newer version before it is overwritten, return the path
+    /// of the copy
+    fn backup_newer(path: &Path, version: u32) -> PathBuf {
+        let backup_path = path.with_extension(format!("ron.v{}", version));
+        fs::copy(path, &backup_path)
+            .unwrap_or_else_show(|e| format!("Failed to backup save file {} to {}: {}", path.display(), backup_path.display(), e));
+        backup_path
+    }
+
+    /// Move the save file away so it isn't overwritten, return its new path
+    fn quarantine(path: &Path) -> PathBuf {
+        let timestamp = ::std::time::SystemTime::now()
+            .duration_since(::std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let quarantine_path = path.with_extension(format!("ron.corrupt-{}", timestamp));
+        fs::rename(path, &quarantine_path)
+            .unwrap_or_else_show(|e| format!("Failed to move corrupt save file {} to {}: {}", path.display(), quarantine_path.display(), e));
+        quarantine_path
+    }
+
+    pub fn profile(&self) -> &String {
+        &self.profile
+    }
+
+    /// Create a new profile with default settings except for the display ones
+    pub fn create_profile(&self, profile: &str) -> Result<(), String> {
+        if !valid_profile_name(profile) || profiles().iter().any(|p| p == profile) {
+            return Err(format!("invalid profile name \"{}\"", profile));
+        }
+        let mut save = Save::default();
+        save.profile = profile.to_string();
+        save.fullscreen = self.fullscreen;
+        save.vulkan_device_uuid = self.vulkan_device_uuid;
+        save.save();
+        Ok(())
+    }
+
+    /// Write the profile to a file that can be imported on another computer
+    pub fn export_profile(&self, path: &Path) -> Result<(), String> {
+        let string = ::ron::ser::to_string(&self).unwrap();
+        File::create(path)
+            .and_then(|mut file| file.write_all(string.as_bytes()))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Import a profile file as a new profile named after the file
+    pub fn import_profile(path: &Path) -> Result<String, String> {
+        let profile = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| stem.to_string())
+            .unwrap_or(String::new());
+        if !valid_profile_name(&profile) || profiles().contains(&profile) {
+            return Err(format!("invalid profile name \"{}\"", profile));
+        }
+        let mut save = Save::read(path)?;
+        save.profile = profile.clone();
+        save.save();
+        Ok(profile)
+    }
+
+    /// Path where the corrupt save file has been moved when loading
+    pub fn quarantined(&self) -> Option<&PathBuf> {
+        self.quarantined.as_ref()
+    }
+
+    /// Path where the save file of a newer version has been copied when loading
+    pub fn newer_backup(&self) -> Option<&PathBuf> {
+        self.newer_backup.as_ref()
     }
 
     pub fn set_custom_level_conf_lazy(&mut self, custom_level_conf: CustomLevelConf) {
@@ -195,6 +573,17 @@ impl Save {
         self.field_of_view
     }
 
+    pub fn set_game_speed_lazy(&mut self, game_speed: f32) {
+        if self.game_speed != game_speed {
+            self.game_speed = game_speed;
+            self.save();
+        }
+    }
+
+    pub fn game_speed(&self) -> f32 {
+        self.game_speed
+    }
+
     pub fn set_effect_volume_lazy(&mut self, volume: f32) {
         if self.effect_volume != volume {
             self.effect_volume = volume;
@@ -255,6 +644,33 @@ impl Save {
         if let PossibleInput::VirtualKeyCode(c) = self.input_settings.right {
             if keycode == c { input.push(Input::Direction(Direction::Right)) }
         }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.hook {
+            if keycode == c { input.push(Input::Hook) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.reel_in {
+            if keycode == c { input.push(Input::ReelIn) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.reel_out {
+            if keycode == c { input.push(Input::ReelOut) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.up {
+            if keycode == c { input.push(Input::Direction(Direction::Up)) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.down {
+            if keycode == c { input.push(Input::Direction(Direction::Down)) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.roll_left {
+            if keycode == c { input.push(Input::RollLeft) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.roll_right {
+            if keycode == c { input.push(Input::RollRight) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.dash {
+            if keycode == c { input.push(Input::Dash) }
+        }
+        if let PossibleInput::VirtualKeyCode(c) = self.input_settings.jump {
+            if keycode == c { input.push(Input::Jump) }
+        }
         input
     }
 
@@ -275,6 +691,33 @@ impl Save {
         if let PossibleInput::MouseButton(b) = self.input_settings.right {
             if button == b { input.push(Input::Direction(Direction::Right)) }
         }
+        if let PossibleInput::MouseButton(b) = self.input_settings.hook {
+            if button == b { input.push(Input::Hook) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.reel_in {
+            if button == b { input.push(Input::ReelIn) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.reel_out {
+            if button == b { input.push(Input::ReelOut) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.up {
+            if button == b { input.push(Input::Direction(Direction::Up)) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.down {
+            if button == b { input.push(Input::Direction(Direction::Down)) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.roll_left {
+            if button == b { input.push(Input::RollLeft) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.roll_right {
+            if button == b { input.push(Input::RollRight) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.dash {
+            if button == b { input.push(Input::Dash) }
+        }
+        if let PossibleInput::MouseButton(b) = self.input_settings.jump {
+            if button == b { input.push(Input::Jump) }
+        }
         input
    }
 
@@ -292,6 +735,82 @@ impl Save {
         self.scores.get(&level)
     }
 
+    pub fn scores(&self) -> &HashMap<usize, Score> {
+        &self.scores
+    }
+
+    /// Merge the scores and statistics of an export into the table of its source, return the
+    /// number of imported runs kept
+    ///
+    /// Imported scores are not verified so they are kept apart from the local ones, they never
+    /// open teleports nor give medals.
+    pub fn import_scores(&mut self, source: String, scores: Vec<(usize, Score)>, stats: Vec<::stats::RunStats>) -> usize {
+        let kept = {
+            let imported = self.imported_scores.entry(source).or_insert(ImportedScores::default());
+            for (level, score) in scores {
+                imported.scores.entry(level).or_insert(Score::new()).merge(score);
+            }
+            merge_runs(&mut imported.stats, stats)
+        };
+        self.save();
+        kept
+    }
+
+    /// Scores imported by source
+    pub fn imported_scores(&self) -> &BTreeMap<String, ImportedScores> {
+        &self.imported_scores
+    }
+
+    /// Medal of the best time of the level, if it has target times
+    pub fn medal(&self, level: usize) -> Option<::level::Medal> {
+        let medals = ::CONFIG.levels.get(level).and_then(|level| level.medals.as_ref());
+        let best = self.score(level).and_then(|score| score.bests.first());
+        match (medals, best) {
+            (Some(medals), Some(&best)) => medals.medal(best),
+            _ => None,
+        }
+    }
+
+    /// Whether the teleport to the level is open in the hall
+    pub fn level_unlocked(&self, level: usize) -> bool {
+        if level == 0 {
+            return true;
+        }
+        let has_medals = ::CONFIG.levels.get(level - 1).map(|level| level.medals.is_some()).unwrap_or(false);
+        match ::CONFIG.hall_unlock_medal {
+            Some(required) if has_medals => self.medal(level - 1).map(|medal| medal >= required).unwrap_or(false),
+            _ => self.score(level - 1).is_some(),
+        }
+    }
+
+    pub fn insert_stats(&mut self, stats: ::stats::RunStats) {
+        self.stats.insert(0, stats);
+        self.stats.truncate(::CONFIG.stats_history);
+        self.save();
+    }
+
+    pub fn stats(&self) -> &Vec<::stats::RunStats> {
+        &self.stats
+    }
+
+    pub fn achievements(&self) -> &::achievement::AchievementProgress {
+        &self.achievements
+    }
+
+    /// Not saved right away, kills are saved with the statistics of the attempt
+    pub fn insert_kill(&mut self, kind: ::stats::EnemyKind) {
+        *self.achievements.kills.entry(kind).or_insert(0) += 1;
+    }
+
+    /// Return false if it was already unlocked
+    pub fn unlock_achievement(&mut self, id: &str) -> bool {
+        let unlocked = self.achievements.unlocked.insert(id.to_string());
+        if unlocked {
+            self.save();
+        }
+        unlocked
+    }
+
     /// Do nothing if sensibility hasn't changed
     pub fn set_mouse_sensibility_lazy(&mut self, mouse_sensibility: f32) {
         if self.mouse_sensibility != mouse_sensibility {
@@ -309,19 +828,107 @@ impl Save {
         self.fullscreen
     }
 
+    /// Write the save to a temporary file then rename it over the save file so it is never
+    /// partially written, the previous save file is kept as a backup
     pub fn save(&self) {
+        let dir = profile_path(&self.profile);
+        fs::create_dir_all(&dir)
+            .unwrap_or_else_show(|e| format!("Failed to create profile directory at {}: {}", dir.display(), e));
+        let path = dir.join(FILENAME);
+        let tmp_path = path.with_extension("ron.tmp");
+        let backup_path = path.with_extension("ron.bak");
+
         let string = ::ron::ser::to_string(&self).unwrap();
-        let mut file = File::create(SAVE_PATH.as_path())
-            .unwrap_or_else_show(|e| format!("Failed to create save file at {}: {}", SAVE_PATH.display(), e));
-        file.write_all(string.as_bytes())
-            .unwrap_or_else_show(|e| format!("Failed to write to save file {}: {}", SAVE_PATH.display(), e));
+        {
+            let mut file = File::create(&tmp_path)
+                .unwrap_or_else_show(|e| format!("Failed to create save file at {}: {}", tmp_path.display(), e));
+            file.write_all(string.as_bytes())
+                .unwrap_or_else_show(|e| format!("Failed to write to save file {}: {}", tmp_path.display(), e));
+            file.sync_all()
+                .unwrap_or_else_show(|e| format!("Failed to write to save file {}: {}", tmp_path.display(), e));
+        }
+        if path.exists() {
+            fs::copy(&path, &backup_path)
+                .unwrap_or_else_show(|e| format!("Failed to backup save file to {}: {}", backup_path.display(), e));
+        }
+        fs::rename(&tmp_path, &path)
+            .unwrap_or_else_show(|e| format!("Failed to replace save file {}: {}", path.display(), e));
     }
 }
 
 pub struct UpdateTime(pub f32);
 
+/// Scale applied to the wall-clock time to compute the update time
+pub struct TimeScale {
+    pub scale: f32,
+    /// Remaining wall-clock duration of the hit stop
+    pub hit_stop: f32,
+    /// Wall-clock duration of the last update
+    pub real_time: f32,
+}
+
+impl TimeScale {
+    pub fn new() -> Self {
+        TimeScale {
+            scale: 1.0,
+            hit_stop: 0.0,
+            real_time: 0.0,
+        }
+    }
+
+    pub fn hit_stop(&mut self) {
+        self.hit_stop = ::CONFIG.hit_stop_duration;
+    }
+
+    /// Update the scale and return the scaled update time
+    pub fn update(&mut self, real_time: f32, game_speed: f32, slow_motion: bool) -> f32 {
+        self.real_time = real_time;
+        self.hit_stop = (self.hit_stop - real_time).max(0.0);
+        self.scale = game_speed;
+        if slow_motion {
+            self.scale *= ::CONFIG.pickup_slow_motion_coef;
+        }
+        if self.hit_stop > 0.0 {
+            self.scale *= ::CONFIG.hit_stop_scale;
+        }
+        real_time * self.scale
+    }
+}
+
 pub struct GameDuration(pub Duration);
 
+/// Seed of the game random generator for the current level
+pub struct LevelSeed(pub u32);
+
+/// Positions of dynamic bodies before the last update, used to draw between updates
+pub struct Interpolation {
+    /// Part of an update step elapsed since the last update
+    pub alpha: f32,
+    pub previous: HashMap<::specs::Entity, ::na::Isometry3<f32>>,
+}
+
+impl Interpolation {
+    pub fn new() -> Self {
+        Interpolation {
+            alpha: 1.0,
+            previous: HashMap::new(),
+        }
+    }
+
+    pub fn position(&self, entity: ::specs::Entity, current: &::na::Isometry3<f32>) -> ::na::Isometry3<f32> {
+        if let Some(previous) = self.previous.get(&entity) {
+            let translation = previous.translation.vector * (1.0 - self.alpha)
+                + current.translation.vector * self.alpha;
+            let rotation = previous.rotation
+                .try_slerp(&current.rotation, self.alpha, 1.0e-6)
+                .unwrap_or(current.rotation);
+            ::na::Isometry3::from_parts(::na::Translation::from_vector(translation), rotation)
+        } else {
+            current.clone()
+        }
+    }
+}
+
 pub struct Rendering {
     pub image_num: Option<usize>,
     pub command_buffer: Option<AutoCommandBuffer>,
@@ -363,9 +970,42 @@ pub struct DebugMode(pub bool);
 
 pub struct DepthCoef(pub f32);
 
+/// Remaining durations of the effects applied to the player
+#[derive(Default)]
+pub struct PlayerEffects {
+    pub slow: f32,
+    pub blind: f32,
+    pub invert: f32,
+}
+
+/// Remaining durations of the power-ups collected by the player
+#[derive(Default)]
+pub struct PowerUps {
+    pub speed_boost: f32,
+    pub slow_motion: f32,
+    pub invulnerability: f32,
+}
+
+/// Keys picked up by the player in the current level
+#[derive(Default)]
+pub struct Keys(pub HashSet<usize>);
+
 pub struct PlayerControl {
     pub directions: Vec<::util::Direction>,
     pub pointer: [f32; 2],
+    /// Six degrees of freedom without gravity, set by the level
+    pub zero_gravity: bool,
+    /// Orientation used in zero gravity instead of pointer
+    pub orientation: ::na::UnitQuaternion<f32>,
+    pub roll_left: bool,
+    pub roll_right: bool,
+    /// Jump is only allowed on levels with a floor, set by the level
+    pub can_jump: bool,
+    pub jump: bool,
+    /// Remaining time of the current dash
+    pub dash_timer: f32,
+    /// Remaining time before next dash
+    pub dash_cooldown: f32,
 }
 
 impl PlayerControl {
@@ -373,8 +1013,24 @@ impl PlayerControl {
         PlayerControl {
             directions: vec![],
             pointer: [0.0, 0.0],
+            zero_gravity: false,
+            orientation: ::na::one(),
+            roll_left: false,
+            roll_right: false,
+            can_jump: false,
+            jump: false,
+            dash_timer: 0.0,
+            dash_cooldown: 0.0,
         }
     }
+
+    /// Start zero gravity orientation from pointer
+    pub fn reset_orientation(&mut self) {
+        self.orientation = ::na::UnitQuaternion::from_rotation_matrix(
+            &(::na::Rotation3::new(::na::Vector3::new(0.0, 0.0, -self.pointer[0]))
+                * ::na::Rotation3::new(::na::Vector3::new(0.0, self.pointer[1], 0.0))),
+        );
+    }
 }
 
 pub struct LevelActions(pub Vec<LevelAction>);
@@ -386,6 +1042,53 @@ pub enum LevelAction {
     Reset,
     ReturnHall,
     Level(usize),
+    /// Play the best run of the level back
+    Replay(usize),
+}
+
+/// Gameplay events of the last update, systems emit them and subscribers read them once the
+/// update is done
+pub struct GameEvents(pub Vec<GameEvent>);
+
+#[derive(Clone)]
+pub enum GameEvent {
+    EnemyKilled {
+        kind: ::stats::EnemyKind,
+        pos: ::na::Vector3<f32>,
+    },
+    PlayerDied {
+        cause: DeathCause,
+    },
+    /// Hit is true if the shot killed at least one entity
+    ShotFired {
+        hit: bool,
+    },
+    HookAttached,
+    /// All enemies are killed and the portal can be used
+    PortalActivated,
+    /// The score of the level has been accepted
+    LevelCompleted {
+        level: usize,
+        time: Duration,
+        stats: ::stats::RunStats,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub enum DeathCause {
+    Enemy,
+    Hazard,
+}
+
+/// Entities to be created by generators, they are created by the game system as it requires
+/// the whole world
+pub struct GeneratorSpawns(pub Vec<GeneratorSpawn>);
+
+pub struct GeneratorSpawn {
+    pub conf: ::entity::EntityConf,
+    pub pos: ::na::Vector3<f32>,
+    pub generator: ::specs::Entity,
+    pub flocking: bool,
 }
 
 pub enum Maze {
@@ -404,6 +1107,13 @@ impl Maze {
             Maze::Maze3D(ref maze) => maze.find_path(pos, goal),
         }
     }
+
+    pub fn open_door(&mut self, pos: &::na::Vector3<f32>) {
+        match *self {
+            Maze::Maze2D(ref mut maze) => maze.open_door(pos),
+            Maze::Maze3D(ref mut maze) => maze.open_door(pos),
+        }
+    }
 }
 
 pub enum MenuStateState {
@@ -413,9 +1123,13 @@ pub enum MenuStateState {
     Help,
     Restart,
     CreateCustom,
+    Profiles,
+    Statistics,
+    Achievements,
 }
 
 #[derive(PartialEq, Deserialize, Serialize, Clone)]
+#[serde(default)]
 pub struct CustomLevelConf {
     pub maze_size: i32,
     pub x_shift: bool,
@@ -432,8 +1146,8 @@ pub struct CustomLevelConf {
     pub turret: i32,
 }
 
-impl CustomLevelConf {
-    pub fn default() -> Self {
+impl Default for CustomLevelConf {
+    fn default() -> Self {
         CustomLevelConf {
             maze_size: 10,
             x_shift: false,
@@ -468,17 +1182,50 @@ pub struct MenuState {
     pub help_ok_button: bool,
     pub help_button: bool,
     pub set_right_button: bool,
+    pub set_hook_button: bool,
+    pub set_reel_in_button: bool,
+    pub set_reel_out_button: bool,
+    pub set_up_button: bool,
+    pub set_down_button: bool,
+    pub set_roll_left_button: bool,
+    pub set_roll_right_button: bool,
+    pub set_dash_button: bool,
+    pub set_jump_button: bool,
     pub quit_button: bool,
     pub levels_button: [bool; 16],
     pub vulkan_device: [u8; 16],
     pub effect_volume_slider: f32,
     pub music_volume_slider: f32,
     pub field_of_view_slider: f32,
+    pub game_speed_slider: f32,
 
     pub create_custom_button: bool,
     pub custom_return_button: bool,
     pub custom_play_button: bool,
     pub custom_level_conf: CustomLevelConf,
+
+    pub profiles_button: bool,
+    pub profiles: Vec<String>,
+    pub profile_buttons: Vec<bool>,
+    pub profile_name_input: ImString,
+    pub create_profile_button: bool,
+    pub profile_file_input: ImString,
+    pub import_profile_button: bool,
+    pub export_profile_button: bool,
+    pub profiles_return_button: bool,
+    /// Result of the last profile creation, import or export
+    pub profile_status: String,
+
+    pub statistics_button: bool,
+    pub statistics_return_button: bool,
+    pub scores_file_input: ImString,
+    pub export_scores_button: bool,
+    pub import_scores_button: bool,
+    /// Result of the last scores export or import
+    pub scores_status: String,
+
+    pub achievements_button: bool,
+    pub achievements_return_button: bool,
 }
 
 impl MenuState {
@@ -488,12 +1235,16 @@ impl MenuState {
             MenuStateState::Pause => true,
             MenuStateState::Restart => true,
             MenuStateState::CreateCustom => true,
+            MenuStateState::Profiles => true,
+            MenuStateState::Statistics => true,
+            MenuStateState::Achievements => true,
             MenuStateState::Game => false,
             MenuStateState::Help => true,
         }
     }
 
     pub fn new(save: &Save) -> Self {
+        let profiles = profiles();
         MenuState {
             state: MenuStateState::Game,
             mouse_sensibility_input: save.mouse_sensibility(),
@@ -510,7 +1261,17 @@ impl MenuState {
             set_backward_button: false,
             set_left_button: false,
             field_of_view_slider: save.field_of_view(),
+            game_speed_slider: save.game_speed(),
             set_right_button: false,
+            set_hook_button: false,
+            set_reel_in_button: false,
+            set_reel_out_button: false,
+            set_up_button: false,
+            set_down_button: false,
+            set_roll_left_button: false,
+            set_roll_right_button: false,
+            set_dash_button: false,
+            set_jump_button: false,
             return_hall_button: false,
             quit_button: false,
             levels_button: [false; 16],
@@ -521,10 +1282,37 @@ impl MenuState {
             custom_return_button: false,
             custom_play_button: false,
             custom_level_conf: save.custom_level_conf(),
+
+            profiles_button: false,
+            profile_buttons: vec![false; profiles.len()],
+            profiles,
+            profile_name_input: ImString::with_capacity(32),
+            create_profile_button: false,
+            profile_file_input: ImString::with_capacity(256),
+            import_profile_button: false,
+            export_profile_button: false,
+            profiles_return_button: false,
+            profile_status: String::new(),
+
+            statistics_button: false,
+            statistics_return_button: false,
+            scores_file_input: ImString::with_capacity(256),
+            export_scores_button: false,
+            import_scores_button: false,
+            scores_status: String::new(),
+
+            achievements_button: false,
+            achievements_return_button: false,
         }
     }
 
-    pub fn build_ui(&mut self, ui: &::imgui::Ui, save: &Save, vulkan_instance: &VulkanInstance, text: &Text, help: &String) {
+    /// Reload the list of profiles after one is created or imported
+    pub fn refresh_profiles(&mut self) {
+        self.profiles = profiles();
+        self.profile_buttons = vec![false; self.profiles.len()];
+    }
+
+    pub fn build_ui(&mut self, ui: &::imgui::Ui, save: &Save, replays: &Replays, vulkan_instance: &VulkanInstance, text: &Text, help: &String) {
         let (width, height) = ui.imgui().display_size();
         let button_size = (::CONFIG.menu_width - 16.0, 30.0);
         let small_button_size = (80.0, 20.0);
@@ -551,7 +1339,18 @@ impl MenuState {
                         self.return_hall_button = ui.button(&ImString::new(text.return_to_hall.clone()), button_size);
                         self.create_custom_button = ui.button(&ImString::new(text.create_custom_level.clone()), button_size);
                         self.help_button = ui.button(&ImString::new(text.help.clone()), button_size);
+                        self.profiles_button = ui.button(&ImString::new(format!("{} [{}]", text.profiles, save.profile())), button_size);
+                        self.statistics_button = ui.button(&ImString::new(text.statistics.clone()), button_size);
+                        self.achievements_button = ui.button(&ImString::new(text.achievement_list.clone()), button_size);
                         self.quit_button = ui.button(&ImString::new(text.quit.clone()), button_size);
+
+                        ui.separator();
+                        ui.text(&ImString::new(text.best_runs.clone()));
+                        for level in 0..self.levels_button.len().min(::CONFIG.levels.len()) {
+                            self.levels_button[level] = replays.available(level)
+                                && ui.button(&ImString::new(format!("{} {}", text.level, level + 1)), small_button_size);
+                        }
+
                         ui.separator();
                         ui.text(&ImString::new(text.audio.clone()));
                         ui.slider_float(&ImString::new(text.music_volume.clone()), &mut self.effect_volume_slider, 0.0, 1.0).build();
@@ -585,6 +1384,7 @@ impl MenuState {
                         self.reset_button = ui.button(&ImString::new(text.reset.clone()), small_button_size);
 
                         ui.slider_float(&ImString::new(text.field_of_view.clone()), &mut self.field_of_view_slider, 0.1, 2.0).build();
+                        ui.slider_float(&ImString::new(text.game_speed.clone()), &mut self.game_speed_slider, 0.25, 1.0).build();
                         ui.input_float(&ImString::new(text.mouse_sensibility.clone()), &mut self.mouse_sensibility_input).build();
 
                         self.set_shoot_button = ui.button(&ImString::new(text.shoot.clone()), small_button_size);
@@ -607,6 +1407,42 @@ impl MenuState {
                         ui.same_line(0.0);
                         ui.text(format!("[{}]", save.input(Input::Direction(Direction::Right)).text(text)));
 
+                        self.set_hook_button = ui.button(&ImString::new(text.hook.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::Hook).text(text)));
+
+                        self.set_reel_in_button = ui.button(&ImString::new(text.reel_in.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::ReelIn).text(text)));
+
+                        self.set_reel_out_button = ui.button(&ImString::new(text.reel_out.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::ReelOut).text(text)));
+
+                        self.set_up_button = ui.button(&ImString::new(text.up.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::Direction(Direction::Up)).text(text)));
+
+                        self.set_down_button = ui.button(&ImString::new(text.down.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::Direction(Direction::Down)).text(text)));
+
+                        self.set_roll_left_button = ui.button(&ImString::new(text.roll_left.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::RollLeft).text(text)));
+
+                        self.set_roll_right_button = ui.button(&ImString::new(text.roll_right.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::RollRight).text(text)));
+
+                        self.set_dash_button = ui.button(&ImString::new(text.dash.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::Dash).text(text)));
+
+                        self.set_jump_button = ui.button(&ImString::new(text.jump.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        ui.text(format!("[{}]", save.input(Input::Jump).text(text)));
+
                         ui.separator();
                         ui.text(&ImString::new(text.credits.clone()));
                         ui.text("    Guillaume Thiolliere  http://thiolliere.org");
@@ -642,6 +1478,135 @@ impl MenuState {
                         ui.slider_int(&ImString::new(text.turret.clone()), &mut self.custom_level_conf.turret, 0, 100).build();
                     });
             }
+            MenuStateState::Profiles => {
+                ui.window(&ImString::new(text.profiles.clone()))
+                    .collapsible(false)
+                    .size((::CONFIG.menu_width, ::CONFIG.menu_height), ::imgui::ImGuiCond::Always)
+                    .position((width/2.0-::CONFIG.menu_width/2.0, height/2.0-::CONFIG.menu_height/2.0), ::imgui::ImGuiCond::Always)
+                    .resizable(false)
+                    .movable(false)
+                    .build(|| {
+                        for i in 0..self.profiles.len() {
+                            let label = if self.profiles[i] == *save.profile() {
+                                format!("[{}]", self.profiles[i])
+                            } else {
+                                self.profiles[i].clone()
+                            };
+                            self.profile_buttons[i] = ui.button(&ImString::new(label), button_size);
+                        }
+
+                        ui.separator();
+                        ui.input_text(&ImString::new(text.profile_name.clone()), &mut self.profile_name_input).build();
+                        self.create_profile_button = ui.button(&ImString::new(text.create_profile.clone()), small_button_size);
+
+                        ui.separator();
+                        ui.input_text(&ImString::new(text.profile_file.clone()), &mut self.profile_file_input).build();
+                        self.import_profile_button = ui.button(&ImString::new(text.import_profile.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        self.export_profile_button = ui.button(&ImString::new(text.export_profile.clone()), small_button_size);
+                        ui.text(&ImString::new(self.profile_status.clone()));
+
+                        ui.separator();
+                        self.profiles_return_button = ui.button(&ImString::new(text.return_.clone()), button_size);
+                    });
+            }
+            MenuStateState::Statistics => {
+                ui.window(&ImString::new(text.statistics.clone()))
+                    .collapsible(false)
+                    .size((::CONFIG.menu_width, ::CONFIG.menu_height), ::imgui::ImGuiCond::Always)
+                    .position((width/2.0-::CONFIG.menu_width/2.0, height/2.0-::CONFIG.menu_height/2.0), ::imgui::ImGuiCond::Always)
+                    .resizable(false)
+                    .movable(false)
+                    .build(|| {
+                        self.statistics_return_button = ui.button(&ImString::new(text.return_.clone()), button_size);
+                        ui.input_text(&ImString::new(text.scores_file.clone()), &mut self.scores_file_input).build();
+                        self.export_scores_button = ui.button(&ImString::new(text.export_scores.clone()), small_button_size);
+                        ui.same_line(0.0);
+                        self.import_scores_button = ui.button(&ImString::new(text.import_scores.clone()), small_button_size);
+                        ui.text(&ImString::new(self.scores_status.clone()));
+                        for stats in save.stats() {
+                            ui.separator();
+                            ui.text(format!(
+                                "{} {} - {}",
+                                text.level,
+                                stats.level + 1,
+                                if stats.completed { &text.completed } else { &text.failed },
+                            ));
+                            ui.text(format!(
+                                "    {} {}  {} {}  {} {}",
+                                text.shots,
+                                stats.shots,
+                                text.hits,
+                                stats.hits,
+                                text.accuracy,
+                                stats.accuracy()
+                                    .map(|accuracy| format!("{:.0}%", accuracy * 100.0))
+                                    .unwrap_or("-".to_string()),
+                            ));
+                            ui.text(format!(
+                                "    {} {}  {} {}  {} {:.1}",
+                                text.deaths,
+                                stats.deaths,
+                                text.hooks,
+                                stats.hooks,
+                                text.distance,
+                                stats.distance,
+                            ));
+                            if !stats.kills.is_empty() {
+                                let kills = stats.kills.iter()
+                                    .map(|(kind, count)| format!("{} {}", kind.text(text), count))
+                                    .collect::<Vec<_>>();
+                                ui.text(format!("    {} {}", text.kills, kills.join(", ")));
+                            }
+                            let parts = stats.parts.iter()
+                                .map(|duration| format!("{}.{:03}s", duration.as_secs(), duration.subsec_nanos() / 1_000_000))
+                                .collect::<Vec<_>>();
+                            ui.text(format!("    {} {}", text.part_times, parts.join(", ")));
+                        }
+
+                        // Imported scores are not verified, they are shown apart
+                        if !save.imported_scores().is_empty() {
+                            ui.separator();
+                            ui.text(&ImString::new(text.imported_scores_title.clone()));
+                        }
+                        for (source, imported) in save.imported_scores() {
+                            let mut levels = imported.scores.iter()
+                                .filter_map(|(&level, score)| score.bests.first().map(|best| (level, best)))
+                                .collect::<Vec<_>>();
+                            levels.sort_by_key(|&(level, _)| level);
+                            ui.text(format!("{} ({} {})", source, imported.stats.len(), text.runs_kept));
+                            for (level, best) in levels {
+                                ui.text(format!(
+                                    "    {} {}: {}.{:03}s",
+                                    text.level,
+                                    level + 1,
+                                    best.as_secs(),
+                                    best.subsec_nanos() / 1_000_000,
+                                ));
+                            }
+                        }
+                    });
+            }
+            MenuStateState::Achievements => {
+                ui.window(&ImString::new(text.achievement_list.clone()))
+                    .collapsible(false)
+                    .size((::CONFIG.menu_width, ::CONFIG.menu_height), ::imgui::ImGuiCond::Always)
+                    .position((width/2.0-::CONFIG.menu_width/2.0, height/2.0-::CONFIG.menu_height/2.0), ::imgui::ImGuiCond::Always)
+                    .resizable(false)
+                    .movable(false)
+                    .build(|| {
+                        self.achievements_return_button = ui.button(&ImString::new(text.return_.clone()), button_size);
+                        let unlocked = &save.achievements().unlocked;
+                        ui.text(format!("{}/{}", unlocked.len(), ::achievement::ACHIEVEMENTS.len()));
+                        for achievement in ::achievement::ACHIEVEMENTS.iter() {
+                            ui.separator();
+                            let (name, description) = text.achievement(&achievement.id);
+                            let mark = if unlocked.contains(&achievement.id) { "[x]" } else { "[ ]" };
+                            ui.text(format!("{} {}", mark, name));
+                            ui.text(format!("    {}", description));
+                        }
+                    });
+            }
             _ => (),
         }
 
@@ -706,12 +1671,60 @@ pub struct Text {
     pub controls: String,
     pub reset: String,
     pub field_of_view: String,
+    pub game_speed: String,
     pub mouse_sensibility: String,
     pub shoot: String,
     pub forward: String,
     pub backward: String,
     pub right: String,
     pub left: String,
+    pub hook: String,
+    pub reel_in: String,
+    pub reel_out: String,
+    pub up: String,
+    pub down: String,
+    pub roll_left: String,
+    pub roll_right: String,
+    pub dash: String,
+    pub jump: String,
+    pub best_runs: String,
+    pub level: String,
+    pub profiles: String,
+    pub profile_name: String,
+    pub create_profile: String,
+    pub profile_file: String,
+    pub import_profile: String,
+    pub export_profile: String,
+    pub profile_created: String,
+    pub profile_imported: String,
+    pub profile_exported: String,
+    pub profile_error: String,
+    pub statistics: String,
+    pub completed: String,
+    pub failed: String,
+    pub shots: String,
+    pub hits: String,
+    pub accuracy: String,
+    pub deaths: String,
+    pub hooks: String,
+    pub distance: String,
+    pub kills: String,
+    pub part_times: String,
+    pub scores_file: String,
+    pub export_scores: String,
+    pub import_scores: String,
+    pub scores_exported: String,
+    pub scores_imported: String,
+    pub runs_kept: String,
+    pub scores_error: String,
+    pub imported_scores_title: String,
+    pub achievement_list: String,
+    pub achievement_unlocked: String,
+    /// Name and description of achievements by id
+    pub achievements: HashMap<String, (String, String)>,
+    pub speed_boost: String,
+    pub slow_motion: String,
+    pub invulnerability: String,
     pub credits: String,
     pub custom: String,
     pub play: String,
@@ -737,6 +1750,8 @@ pub struct Text {
     pub avoider: String,
     pub bouncer: String,
     pub motionless: String,
+    pub patroller: String,
+    pub generator: String,
     pub go_to_portal: String,
     pub remains: String,
     pub mouse_middle: String,
@@ -745,9 +1760,20 @@ pub struct Text {
     pub mouse_other: String,
     pub key: String,
     pub vulkan_error: String,
+    pub corrupt_save: String,
+    pub newer_save: String,
+    pub verifying_run: String,
+    pub run_rejected: String,
 }
 
 impl Text {
+    /// Name and description of the achievement, the id is used if it has no text
+    pub fn achievement<'a>(&'a self, id: &'a str) -> (&'a str, &'a str) {
+        self.achievements.get(id)
+            .map(|&(ref name, ref description)| (&name[..], &description[..]))
+            .unwrap_or((id, ""))
+    }
+
     pub fn load() -> Self {
         let mut texts: Vec<(String, _)> = if cfg!(feature = "packed") {
             vec![
@@ -805,3 +1831,74 @@ fn test_text() {
         }
     }
 }
+
+#[test]
+fn test_save_tolerant_loading() {
+    // Version 0 had no version, statistics nor achievements
+    let save = Save::parse(Cursor::new(
+        "(mouse_sensibility: 0.5, fullscreen: false, scores: {2: (bests: [(secs: 12, nanos: 0)], lasts: [(secs: 12, nanos: 0)])})"
+    )).unwrap();
+    assert_eq!(save.version, SAVE_VERSION);
+    assert_eq!(save.mouse_sensibility, 0.5);
+    assert!(!save.fullscreen);
+    assert_eq!(save.score(2).unwrap().bests, vec![Duration::new(12, 0)]);
+    // Its times were recorded before runs were verified
+    assert!(!save.score(2).unwrap().verified(Duration::new(12, 0)));
+    assert!(save.stats.is_empty());
+    assert!(save.achievements.unlocked.is_empty());
+    assert_eq!(save.game_speed, 1.0);
+}
+
+#[test]
+fn test_score_merge() {
+    fn secs(secs: u64) -> Duration {
+        Duration::new(secs, 0)
+    }
+    let mut score = Score {
+        bests: vec![secs(10), secs(20)],
+        lasts: vec![secs(20), secs(10)],
+        unverified: vec![],
+    };
+    score.merge(Score {
+        bests: vec![secs(5), secs(15), secs(20)],
+        lasts: vec![secs(15), secs(5), secs(20)],
+        unverified: vec![secs(15), secs(20)],
+    });
+    assert_eq!(score.bests, vec![secs(5), secs(10), secs(15), secs(20)]);
+    assert_eq!(score.lasts, vec![secs(20), secs(10), secs(15), secs(5)]);
+    // A time already recorded stays verified
+    assert_eq!(score.unverified, vec![secs(15)]);
+
+    // Local records are kept before the merged ones
+    let mut score = Score {
+        bests: (1..11).map(secs).collect(),
+        lasts: (1..11).map(secs).collect(),
+        unverified: vec![],
+    };
+    score.merge(Score {
+        bests: vec![secs(100)],
+        lasts: vec![secs(100)],
+        unverified: vec![secs(100)],
+    });
+    assert_eq!(score.bests, (1..11).map(secs).collect::<Vec<_>>());
+    assert_eq!(score.lasts, (1..11).map(secs).collect::<Vec<_>>());
+    assert!(score.unverified.is_empty());
+}
+
+#[test]
+fn test_merge_runs() {
+    let run = |level| ::stats::RunStats { level, ..Default::default() };
+    let history = ::CONFIG.stats_history;
+
+    let mut runs = vec![run(1), run(0)];
+    assert_eq!(merge_runs(&mut runs, vec![run(2), run(1)]), 1);
+    assert_eq!(runs.iter().map(|run| run.level).collect::<Vec<_>>(), vec![2, 1, 0]);
+
+    // Newer runs are kept when the history is full, the oldest ones are dropped
+    let mut runs = (0..history).map(|_| run(0)).collect::<Vec<_>>();
+    assert_eq!(merge_runs(&mut runs, vec![run(1), run(2)]), 2);
+    assert_eq!(runs.len(), history);
+    assert_eq!(runs[0].level, 1);
+    assert_eq!(runs[1].level, 2);
+    assert_eq!(runs[2].level, 0);
+}
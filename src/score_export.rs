@@ -0,0 +1,168 @@
+use resource::{Save, Score};
+use stats::RunStats;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+const EXPORT_VERSION: u32 = 1;
+
+/// Scores and statistics of a profile, exported to be merged on another machine
+#[derive(Serialize, Deserialize)]
+pub struct ScoreExport {
+    pub version: u32,
+    pub profile: String,
+    pub levels: Vec<LevelScores>,
+    /// Statistics of the last attempts, most recent first
+    pub stats: Vec<RunStats>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LevelScores {
+    pub level: usize,
+    pub bests: Vec<Time>,
+    pub lasts: Vec<Time>,
+    /// Best time of each part over the completed attempts recorded in the statistics
+    pub splits: Vec<Duration>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Time {
+    pub duration: Duration,
+    /// Whether the run of this time passed the verification
+    pub verified: bool,
+}
+
+impl ScoreExport {
+    /// Only local scores are exported
+    pub fn new(save: &Save) -> Self {
+        let mut levels = save.scores().iter()
+            .map(|(&level, score)| {
+                let times = |durations: &Vec<Duration>| durations.iter()
+                    .map(|&duration| Time {
+                        duration,
+                        verified: score.verified(duration),
+                    })
+                    .collect::<Vec<_>>();
+                LevelScores {
+                    level,
+                    bests: times(&score.bests),
+                    lasts: times(&score.lasts),
+                    splits: best_splits(level, save.stats()),
+                }
+            })
+            .collect::<Vec<_>>();
+        levels.sort_by_key(|level_scores| level_scores.level);
+
+        ScoreExport {
+            version: EXPORT_VERSION,
+            profile: save.profile().clone(),
+            levels,
+            stats: save.stats().clone(),
+        }
+    }
+
+    /// One row per score, split and attempt, durations are in seconds
+    pub fn to_csv(&self) -> String {
+        let mut csv = "level,kind,index,seconds,verified,completed,shots,hits,deaths,hooks,distance,kills\n".to_string();
+        for level_scores in &self.levels {
+            let rows = level_scores.bests.iter().map(|time| ("best", time.duration, Some(time.verified)))
+                .enumerate()
+                .chain(level_scores.lasts.iter().map(|time| ("last", time.duration, Some(time.verified))).enumerate())
+                .chain(level_scores.splits.iter().map(|&duration| ("split", duration, None)).enumerate());
+            for (index, (kind, duration, verified)) in rows {
+                csv.push_str(&format!(
+                    "{},{},{},{:.3},{},,,,,,,\n",
+                    level_scores.level + 1,
+                    kind,
+                    index,
+                    seconds(duration),
+                    verified.map(|verified| verified.to_string()).unwrap_or_default(),
+                ));
+            }
+        }
+        for (index, run) in self.stats.iter().enumerate() {
+            let duration = run.parts.iter().fold(Duration::new(0, 0), |total, &part| total + part);
+            let kills = run.kills.iter()
+                .map(|(kind, count)| format!("{:?}:{}", kind, count))
+                .collect::<Vec<_>>();
+            csv.push_str(&format!(
+                "{},run,{},{:.3},,{},{},{},{},{},{:.1},{}\n",
+                run.level + 1,
+                index,
+                seconds(duration),
+                run.completed,
+                run.shots,
+                run.hits,
+                run.deaths,
+                run.hooks,
+                run.distance,
+                kills.join(";"),
+            ));
+        }
+        csv
+    }
+}
+
+fn seconds(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + duration.subsec_nanos() as f64 * 1e-9
+}
+
+fn best_splits(level: usize, stats: &Vec<RunStats>) -> Vec<Duration> {
+    let mut splits: Vec<Duration> = vec![];
+    for run in stats.iter().filter(|run| run.level == level && run.completed) {
+        for (part, &duration) in run.parts.iter().enumerate() {
+            if part < splits.len() {
+                splits[part] = splits[part].min(duration);
+            } else {
+                splits.push(duration);
+            }
+        }
+    }
+    splits
+}
+
+/// Write the scores of the save as CSV if the file has the csv extension, as JSON otherwise
+pub fn export(save: &Save, path: &Path) -> Result<(), String> {
+    let export = ScoreExport::new(save);
+    let string = if path.extension().map(|extension| extension == "csv").unwrap_or(false) {
+        export.to_csv()
+    } else {
+        ::serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?
+    };
+    File::create(path)
+        .and_then(|mut file| file.write_all(string.as_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+/// Merge the scores of a JSON export into the save apart from the local ones, the source is
+/// named after the file, return the number of imported runs kept
+pub fn import(save: &mut Save, path: &Path) -> Result<usize, String> {
+    let source = path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(|stem| stem.to_string())
+        .ok_or_else(|| format!("invalid file name {}", path.display()))?;
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let export: ScoreExport = ::serde_json::from_reader(file).map_err(|e| e.to_string())?;
+    if export.version > EXPORT_VERSION {
+        return Err(format!("export version {} is newer than {}", export.version, EXPORT_VERSION));
+    }
+
+    let scores = export.levels.into_iter()
+        .filter(|level_scores| level_scores.level < ::CONFIG.levels.len())
+        .map(|level_scores| {
+            let unverified = level_scores.bests.iter()
+                .chain(level_scores.lasts.iter())
+                .filter(|time| !time.verified)
+                .map(|time| time.duration)
+                .collect();
+            let durations = |times: Vec<Time>| times.into_iter().map(|time| time.duration).collect::<Vec<_>>();
+            (level_scores.level, Score {
+                bests: durations(level_scores.bests),
+                lasts: durations(level_scores.lasts),
+                unverified,
+            })
+        })
+        .collect();
+    Ok(save.import_scores(source, scores, export.stats))
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum EnemyKind {
+    Attracted,
+    Avoider,
+    Bouncer,
+    Motionless,
+    Patroller,
+    Generator,
+}
+
+impl EnemyKind {
+    pub fn text<'a>(&self, text: &'a ::resource::Text) -> &'a String {
+        match *self {
+            EnemyKind::Attracted => &text.attracted,
+            EnemyKind::Avoider => &text.avoider,
+            EnemyKind::Bouncer => &text.bouncer,
+            EnemyKind::Motionless => &text.motionless,
+            EnemyKind::Patroller => &text.patroller,
+            EnemyKind::Generator => &text.generator,
+        }
+    }
+}
+
+/// Statistics of one attempt at a level
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct RunStats {
+    pub level: usize,
+    pub completed: bool,
+    pub shots: u32,
+    /// Shots that killed at least one entity
+    pub hits: u32,
+    pub kills: BTreeMap<EnemyKind, u32>,
+    pub deaths: u32,
+    pub hooks: u32,
+    pub distance: f32,
+    /// Time spent in each part, restarts included
+    pub parts: Vec<Duration>,
+}
+
+impl RunStats {
+    pub fn accuracy(&self) -> Option<f32> {
+        if self.shots == 0 {
+            None
+        } else {
+            Some(self.hits as f32 / self.shots as f32)
+        }
+    }
+}
+
+/// Statistics of the attempt being played, nothing is recorded outside of attempts
+pub struct Stats {
+    run: Option<RunStats>,
+    last_position: Option<::na::Vector3<f32>>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats {
+            run: None,
+            last_position: None,
+        }
+    }
+
+    pub fn start_run(&mut self, level: usize) {
+        self.run = Some(RunStats {
+            level,
+            ..Default::default()
+        });
+        self.last_position = None;
+    }
+
+    /// Add the time spent in the part, the player is teleported afterward
+    pub fn end_part(&mut self, part: usize, duration: Duration) {
+        if let Some(ref mut run) = self.run {
+            if run.parts.len() <= part {
+                run.parts.resize(part + 1, Duration::new(0, 0));
+            }
+            run.parts[part] += duration;
+        }
+        self.last_position = None;
+    }
+
+    /// End the attempt and return its statistics if one was recorded
+    pub fn finish(&mut self, completed: bool) -> Option<RunStats> {
+        self.last_position = None;
+        self.run.take().map(|mut run| {
+            run.completed = completed;
+            run
+        })
+    }
+
+    pub fn shot(&mut self, hit: bool) {
+        if let Some(ref mut run) = self.run {
+            run.shots += 1;
+            if hit {
+                run.hits += 1;
+            }
+        }
+    }
+
+    pub fn kill(&mut self, kind: EnemyKind) {
+        if let Some(ref mut run) = self.run {
+            *run.kills.entry(kind).or_insert(0) += 1;
+        }
+    }
+
+    pub fn death(&mut self) {
+        if let Some(ref mut run) = self.run {
+            run.deaths += 1;
+        }
+    }
+
+    pub fn hook(&mut self) {
+        if let Some(ref mut run) = self.run {
+            run.hooks += 1;
+        }
+    }
+
+    pub fn travel(&mut self, position: ::na::Vector3<f32>) {
+        if let Some(ref mut run) = self.run {
+            if let Some(last_position) = self.last_position {
+                run.distance += (position - last_position).norm();
+            }
+            self.last_position = Some(position);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+pub struct AchievementSystem;
+
+impl<'a> ::specs::System<'a> for AchievementSystem {
+    type SystemData = (
+        ::specs::Fetch<'a, ::resource::GameEvents>,
+        ::specs::FetchMut<'a, ::resource::Save>,
+        ::specs::FetchMut<'a, ::resource::Achievements>,
+        ::specs::Fetch<'a, ::resource::Replays>,
+    );
+
+    fn run(&mut self, (game_events, mut save, mut achievements, replays): Self::SystemData) {
+        use resource::GameEvent;
+        use achievement::{Completion, ACHIEVEMENTS};
+
+        for notification in &mut achievements.notifications {
+            notification.1 -= ::CONFIG.update_step_time;
+        }
+        achievements.notifications.retain(|&(_, time)| time > 0.0);
+
+        for event in &game_events.0 {
+            let completion = match *event {
+                // Kills of runs played back don't count, completions only come from the
+                // verification of recorded runs so they are never played back
+                GameEvent::EnemyKilled { kind, .. } if !replays.playing() => {
+                    save.insert_kill(kind);
+                    None
+                },
+                GameEvent::LevelCompleted { level, time, ref stats } => Some(Completion {
+                    level,
+                    time,
+                    stats,
+                }),
+                _ => continue,
+            };
+
+            for achievement in ACHIEVEMENTS.iter() {
+                if !save.achievements().unlocked.contains(&achievement.id)
+                    && achievement.condition.met(save.achievements(), completion.as_ref())
+                    && save.unlock_achievement(&achievement.id)
+                {
+                    achievements.notifications.push((
+                        achievement.id.clone(),
+                        ::CONFIG.achievement_notification_duration,
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -9,19 +9,25 @@ impl<'a> ::specs::System<'a> for ActivateSystem {
         ::specs::ReadStorage<'a, ::component::Avoider>,
         ::specs::ReadStorage<'a, ::component::Bouncer>,
         ::specs::ReadStorage<'a, ::component::Motionless>,
+        ::specs::ReadStorage<'a, ::component::Patroller>,
+        ::specs::ReadStorage<'a, ::component::Generator>,
         ::specs::WriteStorage<'a, ::component::StaticDraw>,
-        ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::Fetch<'a, ::resource::GeneratorSpawns>,
         ::specs::FetchMut<'a, ::resource::Activated>,
+        ::specs::FetchMut<'a, ::resource::GameEvents>,
     );
 
-    fn run(&mut self, (activateds, attracted, avoider, bouncer, motionless, mut static_draws, audio, mut activated): Self::SystemData) {
+    fn run(&mut self, (activateds, attracted, avoider, bouncer, motionless, patroller, generator, mut static_draws, spawns, mut activated, mut game_events): Self::SystemData) {
         if !activated.0
             && attracted.join().next().is_none()
             && avoider.join().next().is_none()
             && bouncer.join().next().is_none()
             && motionless.join().next().is_none()
+            && patroller.join().next().is_none()
+            && generator.join().all(|generator| !generator.prevent_completion())
+            && spawns.0.is_empty()
         {
-            audio.play_unspatial(::audio::Sound::AllKilled);
+            game_events.0.push(::resource::GameEvent::PortalActivated);
             activated.0 = true;
             for (_, draw) in (&activateds, &mut static_draws).join() {
                 draw.color = ::CONFIG.activated_color;
@@ -0,0 +1,91 @@
+use specs::Join;
+use component::AiState;
+
+pub struct AiSystem {
+    collided: Vec<(::specs::Entity, f32)>,
+}
+
+impl AiSystem {
+    pub fn new() -> Self {
+        AiSystem { collided: vec![] }
+    }
+}
+
+impl<'a> ::specs::System<'a> for AiSystem {
+    type SystemData = (
+        ::specs::ReadStorage<'a, ::component::Player>,
+        ::specs::ReadStorage<'a, ::component::PhysicBody>,
+        ::specs::WriteStorage<'a, ::component::Ai>,
+        ::specs::WriteStorage<'a, ::component::Momentum>,
+        ::specs::Fetch<'a, ::resource::PhysicWorld>,
+        ::specs::Fetch<'a, ::resource::UpdateTime>,
+        ::specs::Fetch<'a, ::resource::Maze>,
+    );
+
+    fn run(&mut self, (players, bodies, mut ais, mut momentums, physic_world, update_time, maze): Self::SystemData) {
+        let player_pos = {
+            let (_, player_body) = (&players, &bodies).join().next().unwrap();
+            player_body.get(&physic_world).position().translation.vector
+        };
+
+        for (ai, momentum, body) in (&mut ais, &mut momentums, &bodies).join() {
+            let pos = body.get(&physic_world).position().translation.vector;
+            ai.timer += update_time.0;
+            ai.last_update += update_time.0;
+            ai.patrol_timer -= update_time.0;
+
+            if ai.last_update < 0.0 {
+                continue;
+            }
+            ai.last_update = -::CONFIG.ai_update_time;
+
+            let seen = ::util::first_collided_on_ray(
+                pos,
+                player_pos,
+                ::entity::ATTRACTED_VISION_GROUP,
+                &[::entity::PLAYER_GROUP, ::entity::WALL_GROUP],
+                &physic_world,
+                &mut self.collided,
+            ).iter().any(|&e| players.get(e).is_some());
+
+            if seen {
+                ai.last_seen = Some(player_pos);
+            }
+
+            ai.transition(seen, (player_pos - pos).norm());
+
+            let path_to = |goal: ::na::Vector3<f32>| {
+                let next = maze.find_path(pos, goal)
+                    .and_then(|path| path.get(1).cloned())
+                    .unwrap_or(goal);
+                (next - pos).normalize()
+            };
+
+            momentum.direction = match ai.state {
+                AiState::Idle | AiState::Stunned => ::na::zero(),
+                AiState::Patrol => {
+                    if ai.patrol_timer <= 0.0 {
+                        ai.patrol_timer = ::CONFIG.ai_patrol_turn_time;
+                        let mut random = ::util::random_vector3() - ::na::Vector3::from_element(0.5);
+                        if let ::resource::Maze::Maze2D(_) = *maze {
+                            random[2] = 0.0;
+                        }
+                        ai.patrol_direction = random.normalize();
+                    }
+                    ai.patrol_direction
+                }
+                AiState::Alert => ai.last_seen.map(&path_to).unwrap_or(::na::zero()),
+                AiState::Chase => if seen {
+                    (player_pos - pos).normalize()
+                } else {
+                    path_to(player_pos)
+                },
+                AiState::Flee => (pos - player_pos).normalize(),
+            };
+
+            if momentum.direction.iter().any(|c| c.is_nan()) {
+                momentum.direction = ::na::zero();
+            }
+        }
+    }
+}
@@ -14,51 +14,82 @@ impl<'a> ::specs::System<'a> for AttractedSystem {
     type SystemData = (
         ::specs::ReadStorage<'a, ::component::Player>,
         ::specs::ReadStorage<'a, ::component::PhysicBody>,
+        ::specs::ReadStorage<'a, ::component::Ai>,
         ::specs::WriteStorage<'a, ::component::Attracted>,
         ::specs::WriteStorage<'a, ::component::Momentum>,
         ::specs::Fetch<'a, ::resource::PhysicWorld>,
         ::specs::Fetch<'a, ::resource::UpdateTime>,
         ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::Fetch<'a, ::resource::Maze>,
+        ::specs::Entities<'a>,
     );
 
-    fn run(&mut self, (players, bodies, mut attracteds, mut momentums, physic_world, update_time, audio): Self::SystemData) {
+    fn run(&mut self, (players, bodies, ais, mut attracteds, mut momentums, physic_world, update_time, audio, maze, entities): Self::SystemData) {
         let player_pos = {
             let (_, player_body) = (&players, &bodies).join().next().unwrap();
             player_body.get(&physic_world).position().clone()
         };
 
-        for (attracted, momentum, body) in (&mut attracteds, &mut momentums, &bodies).join() {
+        for (attracted, momentum, body, entity) in (&mut attracteds, &mut momentums, &bodies, &*entities).join() {
+            if ais.get(entity).is_some() {
+                continue;
+            }
+
             let pos = body.get(&physic_world).position();
             attracted.last_update += update_time.0;
+            attracted.search = (attracted.search - update_time.0).max(0.0);
+
+            // Give up going to the last seen position
+            if let Some((_, timer)) = attracted.last_seen {
+                if timer <= update_time.0 {
+                    attracted.last_seen = None;
+                    attracted.search = ::CONFIG.attracted_search_duration;
+                } else {
+                    attracted.last_seen = attracted.last_seen.map(|(p, t)| (p, t - update_time.0));
+                }
+            }
 
             while attracted.last_update >= 0.0 {
                 attracted.last_update -= ::CONFIG.attracted_update_time;
 
-                let ray = ::ncollide::query::Ray {
-                    origin: ::na::Point3::from_coordinates(pos.translation.vector),
-                    dir: player_pos.translation.vector - pos.translation.vector,
-                };
-
-                let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
-                group.set_membership(&[::entity::ATTRACTED_VISION_GROUP]);
-                group.set_whitelist(&[::entity::PLAYER_GROUP, ::entity::WALL_GROUP]);
+                let seen = ::util::first_collided_on_ray(
+                    pos.translation.vector,
+                    player_pos.translation.vector,
+                    ::entity::ATTRACTED_VISION_GROUP,
+                    &[::entity::PLAYER_GROUP, ::entity::WALL_GROUP],
+                    &physic_world,
+                    &mut self.collided,
+                );
+                if seen.iter().any(|&e| players.get(e).is_some()) {
+                    momentum.direction = player_pos.translation.vector - pos.translation.vector;
+                    attracted.last_seen = Some((player_pos.translation.vector, ::CONFIG.attracted_memory_duration));
+                    attracted.search = 0.0;
+                    audio.play(::audio::Sound::Attracted, pos.translation.vector.into());
+                } else if let Some((last_seen, _)) = attracted.last_seen {
+                    if (last_seen - pos.translation.vector).norm() < ::CONFIG.attracted_last_seen_reach_distance {
+                        attracted.last_seen = None;
+                        attracted.search = ::CONFIG.attracted_search_duration;
+                    } else {
+                        let goal = maze.find_path(pos.translation.vector, last_seen)
+                            .and_then(|path| path.get(1).cloned())
+                            .unwrap_or(last_seen);
+                        momentum.direction = (goal - pos.translation.vector).normalize();
+                    }
+                }
 
-                self.collided.clear();
-                for (other_body, collision) in physic_world
-                    .collision_world()
-                    .interferences_with_ray(&ray, &group.as_collision_groups())
-                {
-                    if let ::nphysics::object::WorldObject::RigidBody(other_body) = other_body.data
-                    {
-                        let other_entity = ::component::PhysicBody::entity(physic_world.rigid_body(other_body));
-                        self.collided.push((other_entity, collision.toi));
+                if attracted.last_seen.is_none() {
+                    if attracted.search > 0.0 {
+                        let mut random = ::util::random_vector3() - ::na::Vector3::from_element(0.5);
+                        if let ::resource::Maze::Maze2D(_) = *maze {
+                            random[2] = 0.0;
+                        }
+                        momentum.direction = random.normalize();
+                    } else {
+                        momentum.direction = ::na::zero();
                     }
                 }
-                self.collided.sort_by(|a, b| (a.1).partial_cmp(&b.1).unwrap());
-                if self.collided.first().iter().any(|&&(e, _)| players.get(e).is_some()) {
-                    momentum.direction = ray.dir;
-                    audio.play(::audio::Sound::Attracted, pos.translation.vector.into());
-                } else {
+
+                if momentum.direction.iter().any(|c| c.is_nan()) {
                     momentum.direction = ::na::zero();
                 }
             }
@@ -7,22 +7,28 @@ impl<'a> ::specs::System<'a> for AvoiderControlSystem {
         ::specs::ReadStorage<'a, ::component::Player>,
         ::specs::ReadStorage<'a, ::component::Aim>,
         ::specs::ReadStorage<'a, ::component::PhysicBody>,
+        ::specs::ReadStorage<'a, ::component::Ai>,
         ::specs::WriteStorage<'a, ::component::Avoider>,
         ::specs::WriteStorage<'a, ::component::Momentum>,
         ::specs::Fetch<'a, ::resource::PhysicWorld>,
         ::specs::Fetch<'a, ::resource::Maze>,
+        ::specs::Entities<'a>,
     );
 
     fn run(
         &mut self,
-        (players, aims, bodies, mut avoiders, mut momentums, physic_world, maze): Self::SystemData,
+        (players, aims, bodies, ais, mut avoiders, mut momentums, physic_world, maze, entities): Self::SystemData,
     ) {
         let (_, player_aim, player_body) = (&players, &aims, &bodies).join().next().unwrap();
         let player_aim_dir = player_aim.rotation * ::na::Vector3::x();
 
         let player_pos = player_body.get(&physic_world).position().clone();
 
-        for (avoider, momentum, body) in (&mut avoiders, &mut momentums, &bodies).join() {
+        for (avoider, momentum, body, entity) in (&mut avoiders, &mut momentums, &bodies, &*entities).join() {
+            if ais.get(entity).is_some() {
+                continue;
+            }
+
             let avoider_pos = body.get(&physic_world).position().clone();
 
             let recompute_goal = if let Some(goal) = avoider.goal {
@@ -65,7 +71,7 @@ impl<'a> ::specs::System<'a> for AvoiderControlSystem {
                         (avoid_direction, 1f32) //1.0/avoid_norm)
                     }
                 } else {
-                    let random = ::na::Vector3::new_random();
+                    let random = ::util::random_vector3();
                     // TODO: coefficient
                     (
                         (random - random.dot(&player_aim_dir) * player_aim_dir).normalize(),
@@ -6,13 +6,15 @@ impl<'a> ::specs::System<'a> for BouncerControlSystem {
     type SystemData = (
         ::specs::ReadStorage<'a, ::component::Contactor>,
         ::specs::ReadStorage<'a, ::component::Bouncer>,
+        ::specs::ReadStorage<'a, ::component::Ai>,
         ::specs::WriteStorage<'a, ::component::Momentum>,
         ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::Entities<'a>,
     );
 
-    fn run(&mut self, (contactors, bouncers, mut momentums, audio): Self::SystemData) {
-        for (_, momentum, contactor) in (&bouncers, &mut momentums, &contactors).join() {
-            if contactor.contacts.is_empty() {
+    fn run(&mut self, (contactors, bouncers, ais, mut momentums, audio, entities): Self::SystemData) {
+        for (_, momentum, contactor, entity) in (&bouncers, &mut momentums, &contactors, &*entities).join() {
+            if contactor.contacts.is_empty() || ais.get(entity).is_some() {
                 continue;
             }
 
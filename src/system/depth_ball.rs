@@ -1,4 +1,5 @@
 use specs::Join;
+use component::ProjectileEffect;
 
 pub struct DepthBallSystem;
 
@@ -7,26 +8,62 @@ impl<'a> ::specs::System<'a> for DepthBallSystem {
         ::specs::ReadStorage<'a, ::component::Contactor>,
         ::specs::ReadStorage<'a, ::component::Player>,
         ::specs::ReadStorage<'a, ::component::DepthBall>,
+        ::specs::ReadStorage<'a, ::component::Momentum>,
+        ::specs::WriteStorage<'a, ::component::PhysicBody>,
         ::specs::WriteStorage<'a, ::component::Life>,
         ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::Fetch<'a, ::resource::UpdateTime>,
         ::specs::FetchMut<'a, ::resource::DepthCoef>,
+        ::specs::FetchMut<'a, ::resource::PlayerEffects>,
+        ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+        ::specs::Fetch<'a, ::resource::PowerUps>,
     );
 
-    fn run(&mut self, (contactors, players, depth_balls, mut lifes, audio, mut depth_coef): Self::SystemData) {
-        for (_, life, contactor) in (&depth_balls, &mut lifes, &contactors).join() {
+    fn run(
+        &mut self,
+        (
+            contactors,
+            players,
+            depth_balls,
+            momentums,
+            mut bodies,
+            mut lifes,
+            audio,
+            update_time,
+            mut depth_coef,
+            mut player_effects,
+            mut physic_world,
+            power_ups,
+        ): Self::SystemData,
+    ) {
+        player_effects.slow = (player_effects.slow - update_time.0).max(0.0);
+        player_effects.blind = (player_effects.blind - update_time.0).max(0.0);
+        player_effects.invert = (player_effects.invert - update_time.0).max(0.0);
+
+        for (depth_ball, life, contactor, momentum) in (&depth_balls, &mut lifes, &contactors, &momentums).join() {
             if contactor.contacts.is_empty() {
                 continue;
             }
 
             life.kill();
-            let mut attack = false;
-            if contactor.contacts.iter().any(|&(e, _)| players.get(e).is_some()) {
-                depth_coef.0 /= ::CONFIG.depth_coef_divider;
-                attack = true;
-                audio.play_unspatial(::audio::Sound::DepthBallAttack);
-            }
+            let player_entity = contactor.contacts.iter()
+                .map(|&(e, _)| e)
+                .find(|&e| players.get(e).is_some());
 
-            if !attack {
+            if let Some(player_entity) = player_entity.filter(|_| power_ups.invulnerability == 0.0) {
+                match depth_ball.effect {
+                    ProjectileEffect::Depth => depth_coef.0 /= ::CONFIG.depth_coef_divider,
+                    ProjectileEffect::Slow => player_effects.slow = ::CONFIG.slow_effect_duration,
+                    ProjectileEffect::Blind => player_effects.blind = ::CONFIG.blind_effect_duration,
+                    ProjectileEffect::Invert => player_effects.invert = ::CONFIG.invert_effect_duration,
+                    ProjectileEffect::Push => {
+                        let player_body = bodies.get_mut(player_entity).unwrap().get_mut(&mut physic_world);
+                        let velocity = player_body.lin_vel() + momentum.direction * ::CONFIG.push_effect_velocity;
+                        player_body.set_lin_vel(velocity);
+                    }
+                }
+                audio.play_unspatial(depth_ball.effect.attack_sound());
+            } else {
                 audio.play(::audio::Sound::DepthBallBirthDeath, contactor.contacts.first().unwrap().1.world1.coords.into());
             }
         }
@@ -0,0 +1,37 @@
+use specs::Join;
+use component::DoorLock;
+
+pub struct DoorSystem;
+
+impl<'a> ::specs::System<'a> for DoorSystem {
+    type SystemData = (
+        ::specs::ReadStorage<'a, ::component::Door>,
+        ::specs::WriteStorage<'a, ::component::PhysicBody>,
+        ::specs::Fetch<'a, ::resource::Keys>,
+        ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::FetchMut<'a, ::resource::Maze>,
+        ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+        ::specs::Entities<'a>,
+    );
+
+    fn run(
+        &mut self,
+        (doors, mut bodies, keys, audio, mut maze, mut physic_world, entities): Self::SystemData,
+    ) {
+        for (door, body, entity) in (&doors, &mut bodies, &*entities).join() {
+            let open = match door.lock {
+                DoorLock::Switch(switch) => !entities.is_alive(switch),
+                DoorLock::Clear(ref monsters) => monsters.iter().all(|&m| !entities.is_alive(m)),
+                DoorLock::Key(key) => keys.0.contains(&key),
+            };
+
+            if open {
+                let pos = body.get(&physic_world).position().translation.vector;
+                maze.open_door(&pos);
+                audio.play(::audio::Sound::Door, pos.into());
+                body.remove(&mut physic_world);
+                entities.delete(entity).unwrap();
+            }
+        }
+    }
+}
@@ -30,6 +30,17 @@ impl<'a> ::specs::System<'a> for DrawSystem {
         ::specs::Fetch<'a, ::resource::VulkanInstance>,
         ::specs::Fetch<'a, ::resource::UpdateTime>,
         ::specs::Fetch<'a, ::resource::DepthCoef>,
+        ::specs::Fetch<'a, ::resource::PlayerEffects>,
+        // Grouped as system data tuples are limited to 26 elements
+        (
+            ::specs::Fetch<'a, ::resource::PowerUps>,
+            ::specs::Fetch<'a, ::resource::PlayerControl>,
+            ::specs::Fetch<'a, ::resource::Interpolation>,
+            ::specs::Fetch<'a, ::resource::Replays>,
+            ::specs::Entities<'a>,
+            ::specs::Fetch<'a, ::resource::Achievements>,
+            ::specs::Fetch<'a, ::resource::Verifier>,
+        ),
         ::specs::Fetch<'a, ::resource::Benchmarks>,
         ::specs::Fetch<'a, ::resource::PhysicWorld>,
         ::specs::Fetch<'a, ::resource::DebugMode>,
@@ -58,6 +69,8 @@ impl<'a> ::specs::System<'a> for DrawSystem {
             vulkan_instance,
             update_time,
             depth_coef,
+            player_effects,
+            (power_ups, player_control, interpolation, replays, entities, achievements, verifier),
             benchmarks,
             physic_world,
             debug,
@@ -69,9 +82,9 @@ impl<'a> ::specs::System<'a> for DrawSystem {
 
         // Compute view uniform
         let (view_uniform_buffer_subbuffer, hud_view_uniform_buffer_subbuffer) = {
-            let (_, player_aim, player_body) = (&players, &aims, &bodies).join().next().unwrap();
+            let (_, player_aim, player_body, player_entity) = (&players, &aims, &bodies, &*entities).join().next().unwrap();
 
-            let player_pos = player_body.get(&physic_world).position().clone();
+            let player_pos = interpolation.position(player_entity, player_body.get(&physic_world).position());
             let player_aim_dir = player_aim.rotation * ::na::Vector3::x();
             // IDEA: we can do some fun things by changing this value
             let camera_top = player_aim.rotation * ::na::Vector3::z();
@@ -98,7 +111,11 @@ impl<'a> ::specs::System<'a> for DrawSystem {
                 save.field_of_view(),
                 // IDEA: change to 0.0001 it's funny
                 0.05 * depth_coef.0,
-                70.0,
+                if player_effects.blind > 0.0 {
+                    ::CONFIG.blind_effect_far_plane
+                } else {
+                    70.0
+                },
             ).unwrap();
 
             let view_uniform = ::graphics::shader::draw1_vs::ty::View {
@@ -411,7 +428,63 @@ impl<'a> ::specs::System<'a> for DrawSystem {
             size,
             ::CONFIG.dt(),
         );
-        menu_state.build_ui(&ui, &save, &vulkan_instance, &text, &help.0);
+        menu_state.build_ui(&ui, &save, &replays, &vulkan_instance, &text, &help.0);
+        if !menu_state.paused() {
+            let power_ups = [
+                (power_ups.speed_boost, &text.speed_boost),
+                (power_ups.slow_motion, &text.slow_motion),
+                (power_ups.invulnerability, &text.invulnerability),
+                (player_control.dash_cooldown, &text.dash),
+            ];
+            if power_ups.iter().any(|&(timer, _)| timer > 0.0) {
+                ui.window(im_str!("PowerUps"))
+                    .title_bar(false)
+                    .collapsible(false)
+                    .size((::CONFIG.menu_width/2.0, 90.0), ::imgui::ImGuiCond::Always)
+                    .position((10.0, 10.0), ::imgui::ImGuiCond::Always)
+                    .resizable(false)
+                    .movable(false)
+                    .build(|| {
+                        for &(timer, name) in power_ups.iter().filter(|&&(timer, _)| timer > 0.0) {
+                            ui.text(format!("{}: {:.1}", name, timer));
+                        }
+                    });
+            }
+            if !achievements.notifications.is_empty() {
+                let (width, _) = ui.imgui().display_size();
+                ui.window(im_str!("Achievements"))
+                    .title_bar(false)
+                    .collapsible(false)
+                    .size((::CONFIG.menu_width/2.0, 30.0 + 20.0*achievements.notifications.len() as f32), ::imgui::ImGuiCond::Always)
+                    .position((width - ::CONFIG.menu_width/2.0 - 10.0, 10.0), ::imgui::ImGuiCond::Always)
+                    .resizable(false)
+                    .movable(false)
+                    .build(|| {
+                        ui.text(&text.achievement_unlocked);
+                        for &(ref id, _) in &achievements.notifications {
+                            ui.text(text.achievement(id).0);
+                        }
+                    });
+            }
+            if verifier.pending() > 0 || verifier.rejection.is_some() {
+                let (width, height) = ui.imgui().display_size();
+                ui.window(im_str!("Verification"))
+                    .title_bar(false)
+                    .collapsible(false)
+                    .size((::CONFIG.menu_width, 90.0), ::imgui::ImGuiCond::Always)
+                    .position((width/2.0 - ::CONFIG.menu_width/2.0, height - 100.0), ::imgui::ImGuiCond::Always)
+                    .resizable(false)
+                    .movable(false)
+                    .build(|| {
+                        if verifier.pending() > 0 {
+                            ui.text(&text.verifying_run);
+                        }
+                        if let Some((ref rejection, _)) = verifier.rejection {
+                            ui.text(format!("{} {} {}", text.run_rejected, text.level, rejection));
+                        }
+                    });
+            }
+        }
         if debug.0 {
             ui.window(im_str!("Debug"))
                 .size((100.0, 100.0), ::imgui::ImGuiCond::FirstUseEver)
@@ -0,0 +1,25 @@
+pub struct EventSoundSystem;
+
+impl<'a> ::specs::System<'a> for EventSoundSystem {
+    type SystemData = (
+        ::specs::Fetch<'a, ::resource::GameEvents>,
+        ::specs::Fetch<'a, ::resource::Audio>,
+    );
+
+    fn run(&mut self, (game_events, audio): Self::SystemData) {
+        use resource::GameEvent;
+        for event in &game_events.0 {
+            match *event {
+                GameEvent::ShotFired { hit } => {
+                    audio.play_unspatial(::audio::Sound::Shoot);
+                    if hit {
+                        audio.play_unspatial(::audio::Sound::Kill);
+                    }
+                }
+                GameEvent::PlayerDied { .. } => audio.play_unspatial(::audio::Sound::Death),
+                GameEvent::PortalActivated => audio.play_unspatial(::audio::Sound::AllKilled),
+                _ => (),
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use specs::Join;
+use std::collections::HashMap;
+
+/// Boids steering added to the direction computed by control systems
+///
+/// Neighbours are found with a grid of cells the size of the flocking radius
+/// so only the 27 surrounding cells are looked at.
+pub struct FlockingSystem {
+    grid: HashMap<(i32, i32, i32), Vec<usize>>,
+    /// Group, position and direction of each flocking entity
+    boids: Vec<(usize, ::na::Vector3<f32>, ::na::Vector3<f32>)>,
+}
+
+impl FlockingSystem {
+    pub fn new() -> Self {
+        FlockingSystem {
+            grid: HashMap::new(),
+            boids: vec![],
+        }
+    }
+
+    fn cell(pos: ::na::Vector3<f32>) -> (i32, i32, i32) {
+        let cell = pos / ::CONFIG.flocking_radius;
+        (cell[0].floor() as i32, cell[1].floor() as i32, cell[2].floor() as i32)
+    }
+}
+
+impl<'a> ::specs::System<'a> for FlockingSystem {
+    type SystemData = (
+        ::specs::ReadStorage<'a, ::component::Flocking>,
+        ::specs::ReadStorage<'a, ::component::PhysicBody>,
+        ::specs::WriteStorage<'a, ::component::Momentum>,
+        ::specs::Fetch<'a, ::resource::PhysicWorld>,
+    );
+
+    fn run(&mut self, (flockings, bodies, mut momentums, physic_world): Self::SystemData) {
+        self.grid.clear();
+        self.boids.clear();
+
+        for (flocking, momentum, body) in (&flockings, &momentums, &bodies).join() {
+            let pos = body.get(&physic_world).position().translation.vector;
+            self.grid.entry(Self::cell(pos)).or_insert_with(Vec::new).push(self.boids.len());
+            self.boids.push((flocking.group, pos, momentum.direction));
+        }
+
+        for (i, (_, momentum, _)) in (&flockings, &mut momentums, &bodies).join().enumerate() {
+            let (group, pos, _) = self.boids[i];
+            let cell = Self::cell(pos);
+
+            let mut separation = ::na::Vector3::new(0.0, 0.0, 0.0);
+            let mut alignment = ::na::Vector3::new(0.0, 0.0, 0.0);
+            let mut center = ::na::Vector3::new(0.0, 0.0, 0.0);
+            let mut count = 0;
+
+            for x in -1..2 {
+                for y in -1..2 {
+                    for z in -1..2 {
+                        let neighbours = match self.grid.get(&(cell.0 + x, cell.1 + y, cell.2 + z)) {
+                            Some(neighbours) => neighbours,
+                            None => continue,
+                        };
+                        for &j in neighbours {
+                            let (other_group, other_pos, other_direction) = self.boids[j];
+                            if j == i || other_group != group {
+                                continue;
+                            }
+
+                            let delta = pos - other_pos;
+                            let distance = delta.norm();
+                            if distance > ::CONFIG.flocking_radius || distance == 0.0 {
+                                continue;
+                            }
+
+                            if distance < ::CONFIG.flocking_separation_radius {
+                                separation += delta / distance
+                                    * (1.0 - distance / ::CONFIG.flocking_separation_radius);
+                            }
+                            alignment += other_direction;
+                            center += other_pos;
+                            count += 1;
+                        }
+                    }
+                }
+            }
+
+            if count == 0 {
+                continue;
+            }
+
+            let cohesion = center / count as f32 - pos;
+            let steering = ::CONFIG.flocking_separation_weight * separation
+                + ::CONFIG.flocking_alignment_weight * alignment / count as f32
+                + ::CONFIG.flocking_cohesion_weight * cohesion;
+
+            let direction = (momentum.direction + steering).normalize();
+            if !direction.iter().any(|c| c.is_nan()) {
+                momentum.direction = direction;
+            }
+        }
+    }
+}
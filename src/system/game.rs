@@ -1,6 +1,6 @@
 use nphysics::resolution::{AccumulatedImpulseSolver, CorrectionMode};
 use std::time::Duration;
-use std::collections::HashMap;
+use util::FixedHashMap;
 
 pub struct GameSystem {
     current_level: Option<Level>,
@@ -27,11 +27,54 @@ impl GameSystem {
             action
         };
 
+        let score_accepted = accept_verified_runs(world);
+
+        // Record the time spent in the part left and the statistics of the attempt when it ends
+        let finished_run = if let Some(Level::Level(level, part)) = self.current_level {
+            let duration = world.read_resource::<::resource::GameDuration>().0;
+            let mut stats = world.write_resource::<::resource::Stats>();
+            let finished = match action {
+                Some(::resource::LevelAction::Next) => {
+                    stats.end_part(part, duration);
+                    if ::CONFIG.levels[level].parts.len() == part + 1 {
+                        stats.finish(true)
+                    } else {
+                        None
+                    }
+                },
+                Some(::resource::LevelAction::Reset) => {
+                    stats.end_part(part, duration);
+                    None
+                },
+                Some(_) => {
+                    stats.end_part(part, duration);
+                    stats.finish(false)
+                },
+                None => None,
+            };
+            if let Some(ref run) = finished {
+                world.write_resource::<::resource::Save>().insert_stats(run.clone());
+            }
+            finished
+        } else {
+            None
+        };
+
         let recreate_level = match (self.current_level, action) {
             (None, _) => Some(Level::Hall),
+            (_, Some(::resource::LevelAction::Replay(level))) => {
+                if world.write_resource::<::resource::Replays>().start_playback(level) {
+                    Some(Level::Level(level, 0))
+                } else {
+                    None
+                }
+            },
             (Some(Level::Hall), Some(::resource::LevelAction::Level(level))) => {
                 world.write_resource::<::resource::Audio>().play_unspatial(::audio::Sound::Portal);
-                if ::CONFIG.levels[level].len() != 0 {
+                if ::CONFIG.levels[level].parts.len() != 0 {
+                    let game_speed = world.read_resource::<::resource::Save>().game_speed();
+                    world.write_resource::<::resource::Replays>().start_run(level, game_speed);
+                    world.write_resource::<::resource::Stats>().start_run(level);
                     Some(Level::Level(level, 0))
                 } else {
                     let mut game_duration = world.write_resource::<::resource::GameDuration>();
@@ -42,18 +85,34 @@ impl GameSystem {
             },
             (Some(Level::Level(level, part)), Some(::resource::LevelAction::Next)) => {
                 world.write_resource::<::resource::Audio>().play_unspatial(::audio::Sound::Portal);
-                if ::CONFIG.levels[level].len() > part + 1 {
+                if ::CONFIG.levels[level].parts.len() > part + 1 {
                     Some(Level::Level(level, part+1))
                 } else {
                     let mut game_duration = world.write_resource::<::resource::GameDuration>();
-                    world.write_resource::<::resource::Save>().insert_score(level, game_duration.0);
+                    let mut replays = world.write_resource::<::resource::Replays>();
+                    // Runs played back are not scored
+                    if replays.playing() {
+                        replays.stop();
+                    } else if let Some(replay) = replays.finish(game_duration.0) {
+                        // Scores are only accepted once the recorded run gives the same result
+                        world.write_resource::<::resource::Verifier>()
+                            .verify(replay, finished_run.unwrap_or_default());
+                    }
                     game_duration.0 = Duration::new(0, 0);
                     Some(Level::Hall)
                 }
             },
             (current_level, Some(::resource::LevelAction::Reset)) => current_level,
-            (_, Some(::resource::LevelAction::Custom)) => Some(Level::Custom),
-            (_, Some(::resource::LevelAction::ReturnHall)) => Some(Level::Hall),
+            (_, Some(::resource::LevelAction::Custom)) => {
+                world.write_resource::<::resource::Replays>().stop();
+                Some(Level::Custom)
+            },
+            (_, Some(::resource::LevelAction::ReturnHall)) => {
+                world.write_resource::<::resource::Replays>().stop();
+                Some(Level::Hall)
+            },
+            // The hall is recreated so that its score boards and teleports show the new score
+            (Some(Level::Hall), None) if score_accepted => Some(Level::Hall),
             (Some(_), None) => None,
 
             (Some(Level::Hall), Some(::resource::LevelAction::Next)) => {
@@ -73,43 +132,23 @@ impl GameSystem {
         };
 
         if let Some(level) = recreate_level {
-            world.write_resource::<::resource::GameDuration>().0 = Duration::new(0, 0);
-            world.write_resource::<::resource::Activated>().0 = false;
-            self.current_level = Some(level);
-
-            let physic_world = {
-                let mut physic_world = ::resource::PhysicWorld::new();
-                *physic_world.constraints_solver() = AccumulatedImpulseSolver::new(
-                    ::CONFIG.accumulated_impulse_solver_step,
-                    CorrectionMode::VelocityAndPosition(
-                        ::CONFIG.correction_mode_a,
-                        ::CONFIG.correction_mode_b,
-                        ::CONFIG.correction_mode_c,
-                    ),
-                    ::CONFIG.accumulated_impulse_solver_joint_corr_factor,
-                    ::CONFIG.accumulated_impulse_solver_rest_eps,
-                    ::CONFIG.accumulated_impulse_solver_num_first_order_iter,
-                    ::CONFIG.accumulated_impulse_solver_num_second_order_iter,
-                );
-                physic_world
+            let seed = match level {
+                Level::Level(_, part) => {
+                    let mut player_control = world.write_resource::<::resource::PlayerControl>();
+                    world.write_resource::<::resource::Replays>().start_part(part, &mut player_control.directions)
+                },
+                Level::Hall | Level::Custom => ::rand::random(),
             };
-
-            world.delete_all();
-            world.add_resource(::resource::Events(vec![]));
-
-            world.write_resource::<::resource::Graphics>().reset_group();
-            world.write_resource::<::resource::ErasedStatus>().clear();
-
-            world.add_resource(::resource::DepthCoef(1.0));
-            world.add_resource(physic_world);
+            self.current_level = Some(level);
+            reset_level(world, seed);
 
             match level {
                 Level::Hall => ::level::create_hall(world),
-                Level::Level(level, part) => ::CONFIG.levels[level][part].create(world),
+                Level::Level(level, part) => ::CONFIG.levels[level].parts[part].create(world),
                 Level::Custom => {
                     let conf = world.read_resource::<::resource::Save>().custom_level_conf();
 
-                    let mut entities = HashMap::new();
+                    let mut entities = FixedHashMap::default();
                     entities.insert(::entity::EntityConf::MotionLess { eraser: false }, conf.motion_less as usize);
                     entities.insert(::entity::EntityConf::MotionLess { eraser: true }, conf.motion_less_eraser as usize);
                     entities.insert(::entity::EntityConf::Attracted { eraser: false }, conf.attracted as usize);
@@ -128,11 +167,139 @@ impl GameSystem {
                             if conf.y_shift { 1 } else { 0 },
                         ),
                         entities,
+                        pickups: FixedHashMap::default(),
+                        doors: vec![],
+                        hazards: FixedHashMap::default(),
+                        weapon: None,
+                        hook: None,
                     }).create(world);
                 },
             }
 
+            if world.read_resource::<::resource::Replays>().has_ghost() {
+                ::entity::create_ghost_w(world);
+            }
+
             world.maintain();
+        } else {
+            create_generator_spawns(world);
+        }
+    }
+}
+
+/// Create entities spawned by generators during the last update
+pub fn create_generator_spawns(world: &mut ::specs::World) {
+    let spawns = ::std::mem::replace(&mut world.write_resource::<::resource::GeneratorSpawns>().0, vec![]);
+    for spawn in spawns {
+        let entity = spawn.conf.create(spawn.pos, world);
+        world.write::<::component::Generated>().insert(entity, ::component::Generated {
+            generator: spawn.generator,
+        });
+        if spawn.flocking {
+            world.write::<::component::Flocking>().insert(entity, ::component::Flocking {
+                group: spawn.generator.id() as usize,
+            });
         }
     }
 }
+
+/// Insert the scores of the runs verified and notify the rejected ones, return whether a score
+/// has been inserted
+fn accept_verified_runs(world: &mut ::specs::World) -> bool {
+    let mut verifier = world.write_resource::<::resource::Verifier>();
+    let mut replays = world.write_resource::<::resource::Replays>();
+    let mut save = world.write_resource::<::resource::Save>();
+    let mut game_events = world.write_resource::<::resource::GameEvents>();
+
+    if let Some((_, ref mut timer)) = verifier.rejection {
+        *timer -= ::CONFIG.update_step_time;
+    }
+    if verifier.rejection.as_ref().map(|&(_, timer)| timer <= 0.0).unwrap_or(false) {
+        verifier.rejection = None;
+    }
+
+    let mut accepted = false;
+    let runs = verifier.verified_runs();
+    for run in runs {
+        let level = run.replay.level;
+        match run.result {
+            Ok(()) => {
+                let best = save.score(level)
+                    .and_then(|score| score.bests.first())
+                    .map(|&best| run.replay.duration < best)
+                    .unwrap_or(true);
+                save.insert_score(level, run.replay.duration);
+                game_events.0.push(::resource::GameEvent::LevelCompleted {
+                    level,
+                    time: run.replay.duration,
+                    stats: run.stats,
+                });
+                if best {
+                    replays.save_best(run.replay);
+                }
+                accepted = true;
+            },
+            Err(e) => {
+                // The replay is kept to investigate why the simulation diverged
+                let path = replays.save_rejected(&run.replay);
+                println!("Run of level {} rejected: {}, replay saved at {}", level, e, path.display());
+                verifier.rejection = Some((
+                    format!("{}: {}\n{}", level + 1, e, path.display()),
+                    ::CONFIG.run_rejected_notification_duration,
+                ));
+            },
+        }
+    }
+    accepted
+}
+
+/// Delete all entities and reset resources before creating a level, the game random generator
+/// is seeded with seed
+pub fn reset_level(world: &mut ::specs::World, seed: u32) {
+    ::util::seed_rng(seed);
+    world.add_resource(::resource::LevelSeed(seed));
+    world.add_resource(::resource::Interpolation::new());
+    world.add_resource(::resource::TimeScale::new());
+    world.add_resource(::resource::GeneratorSpawns(vec![]));
+    world.write_resource::<::resource::GameDuration>().0 = Duration::new(0, 0);
+    world.write_resource::<::resource::Activated>().0 = false;
+
+    let physic_world = {
+        let mut physic_world = ::resource::PhysicWorld::new();
+        *physic_world.constraints_solver() = AccumulatedImpulseSolver::new(
+            ::CONFIG.accumulated_impulse_solver_step,
+            CorrectionMode::VelocityAndPosition(
+                ::CONFIG.correction_mode_a,
+                ::CONFIG.correction_mode_b,
+                ::CONFIG.correction_mode_c,
+            ),
+            ::CONFIG.accumulated_impulse_solver_joint_corr_factor,
+            ::CONFIG.accumulated_impulse_solver_rest_eps,
+            ::CONFIG.accumulated_impulse_solver_num_first_order_iter,
+            ::CONFIG.accumulated_impulse_solver_num_second_order_iter,
+        );
+        physic_world
+    };
+
+    world.delete_all();
+    world.add_resource(::resource::Events(vec![]));
+
+    world.write_resource::<::resource::Graphics>().reset_group();
+    world.write_resource::<::resource::ErasedStatus>().clear();
+
+    world.add_resource(::resource::DepthCoef(1.0));
+    world.add_resource(::resource::PlayerEffects::default());
+    world.add_resource(::resource::PowerUps::default());
+    world.add_resource(::resource::Keys::default());
+    world.add_resource(physic_world);
+    {
+        let mut player_control = world.write_resource::<::resource::PlayerControl>();
+        player_control.zero_gravity = false;
+        player_control.can_jump = false;
+        player_control.dash_timer = 0.0;
+        player_control.dash_cooldown = 0.0;
+        player_control.roll_left = false;
+        player_control.roll_right = false;
+        player_control.jump = false;
+    }
+}
@@ -1,23 +1,15 @@
 use rand::Rand;
 use specs::Join;
-use component::GeneratedEntity;
+use std::collections::HashMap;
 
 pub struct GeneratorSystem;
 
 impl<'a> ::specs::System<'a> for GeneratorSystem {
     type SystemData = (
         ::specs::WriteStorage<'a, ::component::Generator>,
-        ::specs::WriteStorage<'a, ::component::PhysicBody>,
-        ::specs::WriteStorage<'a, ::component::Momentum>,
-        ::specs::WriteStorage<'a, ::component::Avoider>,
-        ::specs::WriteStorage<'a, ::component::Bouncer>,
-        ::specs::WriteStorage<'a, ::component::DynamicEraser>,
-        ::specs::WriteStorage<'a, ::component::DynamicDraw>,
-        ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
-        ::specs::WriteStorage<'a, ::component::Life>,
-        ::specs::WriteStorage<'a, ::component::Contactor>,
-        ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+        ::specs::ReadStorage<'a, ::component::Generated>,
         ::specs::Fetch<'a, ::resource::UpdateTime>,
+        ::specs::FetchMut<'a, ::resource::GeneratorSpawns>,
         ::specs::Entities<'a>,
     );
 
@@ -25,56 +17,37 @@ impl<'a> ::specs::System<'a> for GeneratorSystem {
         &mut self,
         (
             mut generators,
-            mut bodies,
-            mut momentums,
-            mut avoiders,
-            mut bouncers,
-            mut dynamic_erasers,
-            mut dynamic_draws,
-            mut dynamic_graphics_assets,
-            mut lives,
-            mut contactors,
-            mut physic_world,
+            generateds,
             update_time,
+            mut spawns,
             entities,
         ): Self::SystemData,
     ) {
-        let mut rng = ::rand::thread_rng();
+        let mut alives = HashMap::new();
+        for generated in generateds.join() {
+            *alives.entry(generated.generator).or_insert(0) += 1;
+        }
 
-        for mut generator in (&mut generators).join() {
+        for (generator, entity) in (&mut generators, &*entities).join() {
             generator.timer -= update_time.0;
             if generator.timer <= 0.0 {
                 generator.timer = generator.time_between_salvo;
+                let mut alive = alives.get(&entity).cloned().unwrap_or(0);
                 for _ in 0..generator.salvo {
-                    match generator.entity {
-                        GeneratedEntity::Bouncer => ::entity::create_bouncer(
-                            generator.pos,
-                            f32::rand(&mut rng) < generator.eraser_probability,
-                            &mut momentums,
-                            &mut bouncers,
-                            &mut bodies,
-                            &mut dynamic_erasers,
-                            &mut dynamic_draws,
-                            &mut dynamic_graphics_assets,
-                            &mut lives,
-                            &mut contactors,
-                            &mut physic_world,
-                            &entities,
-                        ),
-                        GeneratedEntity::Avoider => ::entity::create_avoider(
-                            generator.pos,
-                            f32::rand(&mut rng) < generator.eraser_probability,
-                            &mut momentums,
-                            &mut avoiders,
-                            &mut bodies,
-                            &mut dynamic_erasers,
-                            &mut dynamic_draws,
-                            &mut dynamic_graphics_assets,
-                            &mut lives,
-                            &mut physic_world,
-                            &entities,
-                        ),
+                    if generator.budget_exhausted()
+                        || generator.max_alive.map(|max| alive >= max).unwrap_or(false)
+                    {
+                        break;
                     }
+                    let eraser = f32::rand(&mut *::util::rng()) < generator.eraser_probability;
+                    spawns.0.push(::resource::GeneratorSpawn {
+                        conf: generator.entity.with_eraser(eraser),
+                        pos: generator.pos,
+                        generator: entity,
+                        flocking: generator.flocking,
+                    });
+                    generator.spawned += 1;
+                    alive += 1;
                 }
             }
         }
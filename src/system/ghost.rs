@@ -0,0 +1,31 @@
+use specs::Join;
+
+pub struct GhostSystem;
+
+impl<'a> ::specs::System<'a> for GhostSystem {
+    type SystemData = (
+        ::specs::ReadStorage<'a, ::component::Ghost>,
+        ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+        ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+        ::specs::FetchMut<'a, ::resource::Replays>,
+        ::specs::Entities<'a>,
+    );
+
+    fn run(
+        &mut self,
+        (ghosts, mut dynamic_draws, mut dynamic_graphics_assets, mut replays, entities): Self::SystemData,
+    ) {
+        for (_, assets, entity) in (&ghosts, &mut dynamic_graphics_assets, &*entities).join() {
+            // The ghost disappears at the end of its part
+            if let Some(position) = replays.ghost_position() {
+                let trans = ::na::Isometry3::new(position, ::na::zero()) * assets.primitive_trans;
+                assets.world_trans = ::graphics::shader::draw1_vs::ty::World {
+                    world: trans.unwrap().into(),
+                };
+                dynamic_draws.insert(entity, ::component::DynamicDraw);
+            } else {
+                dynamic_draws.remove(entity);
+            }
+        }
+    }
+}
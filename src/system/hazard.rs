@@ -0,0 +1,29 @@
+use specs::Join;
+
+pub struct HazardSystem;
+
+impl<'a> ::specs::System<'a> for HazardSystem {
+    type SystemData = (
+        ::specs::WriteStorage<'a, ::component::Hazard>,
+        ::specs::WriteStorage<'a, ::component::DynamicDraw>,
+        ::specs::Fetch<'a, ::resource::UpdateTime>,
+        ::specs::Entities<'a>,
+    );
+
+    fn run(&mut self, (mut hazards, mut dynamic_draws, update_time, entities): Self::SystemData) {
+        for (hazard, entity) in (&mut hazards, &*entities).join() {
+            if let Some((active_time, inactive_time)) = hazard.period {
+                hazard.timer = (hazard.timer + update_time.0) % (active_time + inactive_time);
+                let active = hazard.timer < active_time;
+                if active != hazard.active {
+                    hazard.active = active;
+                    if active {
+                        dynamic_draws.insert(entity, ::component::DynamicDraw);
+                    } else {
+                        dynamic_draws.remove(entity);
+                    }
+                }
+            }
+        }
+    }
+}
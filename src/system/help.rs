@@ -8,16 +8,20 @@ impl<'a> ::specs::System<'a> for HelpSystem {
         ::specs::ReadStorage<'a, ::component::Avoider>,
         ::specs::ReadStorage<'a, ::component::Bouncer>,
         ::specs::ReadStorage<'a, ::component::Motionless>,
+        ::specs::ReadStorage<'a, ::component::Patroller>,
+        ::specs::ReadStorage<'a, ::component::Generator>,
         ::specs::FetchMut<'a, ::resource::Help>,
         ::specs::Fetch<'a, ::resource::Text>,
     );
 
-    fn run(&mut self, (attracted, avoider, bouncer, motionless, mut help, text): Self::SystemData) {
+    fn run(&mut self, (attracted, avoider, bouncer, motionless, patroller, generator, mut help, text): Self::SystemData) {
         let r = [
             (attracted.join().count(), &text.attracted),
             (avoider.join().count(), &text.avoider),
             (bouncer.join().count(), &text.bouncer),
             (motionless.join().count(), &text.motionless),
+            (patroller.join().count(), &text.patroller),
+            (generator.join().filter(|generator| generator.prevent_completion()).count(), &text.generator),
         ];
 
         let remaining = r
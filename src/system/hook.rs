@@ -16,10 +16,12 @@ impl<'a> ::specs::System<'a> for HookSystem {
         ::specs::ReadStorage<'a, ::component::Aim>,
         ::specs::WriteStorage<'a, ::component::Hook>,
         ::specs::Fetch<'a, ::resource::PhysicWorld>,
+        ::specs::Fetch<'a, ::resource::UpdateTime>,
+        ::specs::FetchMut<'a, ::resource::GameEvents>,
         ::specs::Entities<'a>,
     );
 
-    fn run(&mut self, (bodies, aims, mut hooks, physic_world, entities): Self::SystemData) {
+    fn run(&mut self, (bodies, aims, mut hooks, physic_world, update_time, mut game_events, entities): Self::SystemData) {
         for (aim, body, hook, entity) in (&aims, &bodies, &mut hooks, &*entities).join() {
             // Delete anchor if entity doesn't exist anymore
             if let Some(false) = hook.anchor
@@ -27,11 +29,13 @@ impl<'a> ::specs::System<'a> for HookSystem {
                 .map(|anchor| entities.is_alive(anchor.entity))
             {
                 hook.anchor = None;
+                hook.length = None;
                 hook.launch = false;
             }
 
             if !hook.launch && hook.anchor.is_some() {
                 hook.anchor = None;
+                hook.length = None;
             }
 
             if hook.launch && hook.anchor.is_none() {
@@ -62,7 +66,7 @@ impl<'a> ::specs::System<'a> for HookSystem {
                 }
                 self.collided
                     .sort_by(|a, b| (a.1).partial_cmp(&b.1).unwrap());
-                for collided in &self.collided {
+                for collided in self.collided.iter().filter(|c| c.1 <= hook.range) {
                     let other_pos = bodies
                         .get(collided.0)
                         .unwrap()
@@ -75,6 +79,10 @@ impl<'a> ::specs::System<'a> for HookSystem {
                         local_pos,
                         pos: ::na::zero(),
                     });
+                    if hook.mode == ::component::HookMode::Rope {
+                        hook.length = Some(collided.1.max(::CONFIG.player_hook_min_length));
+                    }
+                    game_events.0.push(::resource::GameEvent::HookAttached);
                     break;
                 }
             }
@@ -88,6 +96,17 @@ impl<'a> ::specs::System<'a> for HookSystem {
                     .position() * anchor.local_pos)
                     .coords;
             }
+
+            // Reel
+            if let Some(ref mut length) = hook.length {
+                if hook.reel_in {
+                    *length -= ::CONFIG.player_hook_reel_velocity * update_time.0;
+                }
+                if hook.reel_out {
+                    *length += ::CONFIG.player_hook_reel_velocity * update_time.0;
+                }
+                *length = length.max(::CONFIG.player_hook_min_length).min(hook.range);
+            }
         }
     }
 }
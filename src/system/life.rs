@@ -10,25 +10,59 @@ impl<'a> ::specs::System<'a> for LifeSystem {
         ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
         ::specs::WriteStorage<'a, ::component::Life>,
         ::specs::WriteStorage<'a, ::component::Reducer>,
+        ::specs::WriteStorage<'a, ::component::Ai>,
+        ::specs::ReadStorage<'a, ::component::Attracted>,
+        ::specs::ReadStorage<'a, ::component::Avoider>,
+        ::specs::ReadStorage<'a, ::component::Bouncer>,
+        ::specs::ReadStorage<'a, ::component::Motionless>,
+        ::specs::ReadStorage<'a, ::component::Patroller>,
+        ::specs::ReadStorage<'a, ::component::Generator>,
         ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+        ::specs::FetchMut<'a, ::resource::GameEvents>,
         ::specs::Entities<'a>,
     );
 
     fn run(
         &mut self,
-        (mut bodies, mut dynamic_draws, mut dynamic_erasers, mut dynamic_graphics_assets, mut lives, mut reducers, mut physic_world, entities): Self::SystemData,
+        (mut bodies, mut dynamic_draws, mut dynamic_erasers, mut dynamic_graphics_assets, mut lives, mut reducers, mut ais, attracted, avoider, bouncer, motionless, patroller, generator, mut physic_world, mut game_events, entities): Self::SystemData,
     ) {
         use component::Life;
+        use stats::EnemyKind;
         for (life, entity) in (&mut lives, &*entities).join() {
             match *life {
                 Life::EraserDead => {
                     *life = Life::DrawAlive;
                     dynamic_draws.insert(entity, ::component::DynamicDraw);
                     dynamic_erasers.remove(entity).unwrap();
+                    if let Some(ai) = ais.get_mut(entity) {
+                        ai.damaged = true;
+                    }
                 }
                 Life::DrawDead => {
+                    let kind = if attracted.get(entity).is_some() {
+                        Some(EnemyKind::Attracted)
+                    } else if avoider.get(entity).is_some() {
+                        Some(EnemyKind::Avoider)
+                    } else if bouncer.get(entity).is_some() {
+                        Some(EnemyKind::Bouncer)
+                    } else if motionless.get(entity).is_some() {
+                        Some(EnemyKind::Motionless)
+                    } else if patroller.get(entity).is_some() {
+                        Some(EnemyKind::Patroller)
+                    } else if generator.get(entity).is_some() {
+                        Some(EnemyKind::Generator)
+                    } else {
+                        None
+                    };
                     let body = bodies.get_mut(entity).unwrap();
 
+                    if let Some(kind) = kind {
+                        game_events.0.push(::resource::GameEvent::EnemyKilled {
+                            kind,
+                            pos: body.get(&physic_world).position().translation.vector,
+                        });
+                    }
+
                     let death_animation_assets = {
                         let assets = dynamic_graphics_assets.get(entity).unwrap();
                         let position = body.get(&physic_world).position();
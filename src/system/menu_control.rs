@@ -2,6 +2,7 @@ use winit::{ElementState, Event, MouseButton, MouseScrollDelta, TouchPhase, Virt
             WindowEvent, KeyboardInput};
 use resource::Input;
 use util::Direction;
+use std::path::PathBuf;
 
 pub struct MenuGameControlSystem;
 
@@ -59,9 +60,10 @@ impl<'a> ::specs::System<'a> for MenuPauseControlSystem {
         ::specs::FetchMut<'a, ::resource::MenuState>,
         ::specs::FetchMut<'a, ::resource::Save>,
         ::specs::FetchMut<'a, ::resource::LevelActions>,
+        ::specs::Fetch<'a, ::resource::Text>,
     );
 
-    fn run(&mut self, (events, mut imgui, mut menu_state, mut save, mut level_actions): Self::SystemData) {
+    fn run(&mut self, (events, mut imgui, mut menu_state, mut save, mut level_actions, text): Self::SystemData) {
         let mut imgui = imgui.as_mut().unwrap();
         imgui.set_mouse_draw_cursor(true);
         send_events_to_imgui(&events, &mut imgui, &mut self.mouse_down);
@@ -90,6 +92,74 @@ impl<'a> ::specs::System<'a> for MenuPauseControlSystem {
                     level_actions.0.push(::resource::LevelAction::Custom);
                 }
             }
+            ::resource::MenuStateState::Statistics => {
+                if menu_state.export_scores_button {
+                    let path = PathBuf::from(menu_state.scores_file_input.to_str().trim());
+                    menu_state.scores_status = match ::score_export::export(&save, &path) {
+                        Ok(()) => format!("{} {}", text.scores_exported, path.display()),
+                        Err(e) => format!("{} {}", text.scores_error, e),
+                    };
+                }
+
+                if menu_state.import_scores_button {
+                    let path = PathBuf::from(menu_state.scores_file_input.to_str().trim());
+                    menu_state.scores_status = match ::score_export::import(&mut save, &path) {
+                        Ok(kept) => format!("{} {} ({} {})", text.scores_imported, path.display(), kept, text.runs_kept),
+                        Err(e) => format!("{} {}", text.scores_error, e),
+                    };
+                }
+
+                if menu_state.statistics_return_button {
+                    menu_state.state = ::resource::MenuStateState::Pause;
+                }
+            }
+            ::resource::MenuStateState::Achievements => {
+                if menu_state.achievements_return_button {
+                    menu_state.state = ::resource::MenuStateState::Pause;
+                }
+            }
+            ::resource::MenuStateState::Profiles => {
+                if let Some(i) = menu_state.profile_buttons.iter().position(|&pressed| pressed) {
+                    let profile = menu_state.profiles[i].clone();
+                    if profile == *save.profile() {
+                        menu_state.state = ::resource::MenuStateState::Game;
+                    } else {
+                        // The game restarts to load the save and replays of the profile
+                        ::resource::set_current_profile(&profile);
+                        menu_state.restart_now_button = true;
+                    }
+                }
+
+                if menu_state.create_profile_button {
+                    let profile = menu_state.profile_name_input.to_str().trim().to_string();
+                    menu_state.profile_status = match save.create_profile(&profile) {
+                        Ok(()) => format!("{} {}", text.profile_created, profile),
+                        Err(e) => format!("{} {}", text.profile_error, e),
+                    };
+                    menu_state.refresh_profiles();
+                }
+
+                if menu_state.import_profile_button {
+                    let path = PathBuf::from(menu_state.profile_file_input.to_str().trim());
+                    menu_state.profile_status = match ::resource::Save::import_profile(&path) {
+                        Ok(profile) => format!("{} {}", text.profile_imported, profile),
+                        Err(e) => format!("{} {}", text.profile_error, e),
+                    };
+                    menu_state.refresh_profiles();
+                }
+
+                if menu_state.export_profile_button {
+                    let path = PathBuf::from(menu_state.profile_file_input.to_str().trim());
+                    menu_state.profile_status = match save.export_profile(&path) {
+                        Ok(()) => format!("{} {}", text.profile_exported, path.display()),
+                        Err(e) => format!("{} {}", text.profile_error, e),
+                    };
+                }
+
+                if menu_state.profiles_return_button {
+                    menu_state.state = ::resource::MenuStateState::Pause;
+                }
+            }
             ::resource::MenuStateState::Input(input) => {
                 for ev in events.0.iter() {
                     let received_input = match *ev {
@@ -163,6 +233,11 @@ impl<'a> ::specs::System<'a> for MenuPauseControlSystem {
                     menu_state.state = ::resource::MenuStateState::Game;
                 }
 
+                if let Some(level) = menu_state.levels_button.iter().position(|&pressed| pressed) {
+                    level_actions.0.push(::resource::LevelAction::Replay(level));
+                    menu_state.state = ::resource::MenuStateState::Game;
+                }
+
                 if menu_state.set_shoot_button {
                     menu_state.state = ::resource::MenuStateState::Input(Input::Shoot);
                 }
@@ -183,6 +258,42 @@ impl<'a> ::specs::System<'a> for MenuPauseControlSystem {
                     menu_state.state = ::resource::MenuStateState::Input(Input::Direction(Direction::Right));
                 }
 
+                if menu_state.set_hook_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::Hook);
+                }
+
+                if menu_state.set_reel_in_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::ReelIn);
+                }
+
+                if menu_state.set_reel_out_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::ReelOut);
+                }
+
+                if menu_state.set_up_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::Direction(Direction::Up));
+                }
+
+                if menu_state.set_down_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::Direction(Direction::Down));
+                }
+
+                if menu_state.set_roll_left_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::RollLeft);
+                }
+
+                if menu_state.set_roll_right_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::RollRight);
+                }
+
+                if menu_state.set_dash_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::Dash);
+                }
+
+                if menu_state.set_jump_button {
+                    menu_state.state = ::resource::MenuStateState::Input(Input::Jump);
+                }
+
                 if menu_state.reset_button {
                     save.reset_controls();
                     menu_state.mouse_sensibility_input = save.mouse_sensibility();
@@ -198,6 +309,20 @@ impl<'a> ::specs::System<'a> for MenuPauseControlSystem {
                     menu_state.state = ::resource::MenuStateState::Help;
                 }
 
+                if menu_state.statistics_button {
+                    menu_state.scores_status.clear();
+                    menu_state.state = ::resource::MenuStateState::Statistics;
+                }
+
+                if menu_state.achievements_button {
+                    menu_state.state = ::resource::MenuStateState::Achievements;
+                }
+
+                if menu_state.profiles_button {
+                    menu_state.profile_status.clear();
+                    menu_state.state = ::resource::MenuStateState::Profiles;
+                }
+
                 save.set_mouse_sensibility_lazy(menu_state.mouse_sensibility_input);
 
                 if save.set_vulkan_device_uuid_lazy(&menu_state.vulkan_device) {
@@ -207,6 +332,7 @@ impl<'a> ::specs::System<'a> for MenuPauseControlSystem {
                 save.set_effect_volume_lazy(menu_state.music_volume_slider);
                 save.set_music_volume_lazy(menu_state.effect_volume_slider);
                 save.set_field_of_view_lazy(menu_state.field_of_view_slider);
+                save.set_game_speed_lazy(menu_state.game_speed_slider);
             },
         }
     }
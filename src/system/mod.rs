@@ -1,4 +1,5 @@
 mod attracted;
+mod patroller;
 mod depth_ball;
 mod audio;
 mod depth_coef;
@@ -22,8 +23,26 @@ mod reducer;
 mod activated;
 mod help;
 mod player_death;
+mod pickup;
+mod door;
+mod hazard;
+mod ghost;
+mod flocking;
+mod ai;
+mod event_sound;
+mod stats;
+mod achievement;
 
 pub use self::teleport::TeleportSystem;
+pub use self::pickup::PickupSystem;
+pub use self::door::DoorSystem;
+pub use self::hazard::HazardSystem;
+pub use self::ghost::GhostSystem;
+pub use self::flocking::FlockingSystem;
+pub use self::ai::AiSystem;
+pub use self::event_sound::EventSoundSystem;
+pub use self::stats::StatsSystem;
+pub use self::achievement::AchievementSystem;
 pub use self::menu_control::{MenuGameControlSystem, MenuPauseControlSystem};
 pub use self::player_control::PlayerControlSystem;
 pub use self::avoider_control::AvoiderControlSystem;
@@ -35,7 +54,7 @@ pub use self::draw::DrawSystem;
 pub use self::update_draw_eraser::UpdateDynamicDrawEraserSystem;
 pub use self::life::LifeSystem;
 pub use self::shoot::ShootSystem;
-pub use self::game::GameSystem;
+pub use self::game::{GameSystem, create_generator_spawns, reset_level};
 pub use self::deleter::DeleterSystem;
 pub use self::generator::GeneratorSystem;
 pub use self::hook::HookSystem;
@@ -44,6 +63,7 @@ pub use self::audio::AudioSystem;
 pub use self::depth_coef::DepthCoefSystem;
 pub use self::depth_ball::DepthBallSystem;
 pub use self::attracted::AttractedSystem;
+pub use self::patroller::PatrollerSystem;
 pub use self::activated::ActivateSystem;
 pub use self::player_death::PlayerDeathSystem;
 pub use self::help::HelpSystem;
@@ -0,0 +1,88 @@
+use specs::Join;
+
+pub struct PatrollerSystem {
+    collided: Vec<(::specs::Entity, f32)>,
+}
+
+impl PatrollerSystem {
+    pub fn new() -> Self {
+        PatrollerSystem { collided: vec![] }
+    }
+}
+
+impl<'a> ::specs::System<'a> for PatrollerSystem {
+    type SystemData = (
+        ::specs::ReadStorage<'a, ::component::Player>,
+        ::specs::ReadStorage<'a, ::component::PhysicBody>,
+        ::specs::ReadStorage<'a, ::component::Ai>,
+        ::specs::WriteStorage<'a, ::component::Patroller>,
+        ::specs::WriteStorage<'a, ::component::Momentum>,
+        ::specs::Fetch<'a, ::resource::PhysicWorld>,
+        ::specs::Fetch<'a, ::resource::UpdateTime>,
+        ::specs::Fetch<'a, ::resource::Maze>,
+        ::specs::Entities<'a>,
+    );
+
+    fn run(&mut self, (players, bodies, ais, mut patrollers, mut momentums, physic_world, update_time, maze, entities): Self::SystemData) {
+        let player_pos = {
+            let (_, player_body) = (&players, &bodies).join().next().unwrap();
+            player_body.get(&physic_world).position().translation.vector
+        };
+
+        for (patroller, momentum, body, entity) in (&mut patrollers, &mut momentums, &bodies, &*entities).join() {
+            if ais.get(entity).is_some() {
+                continue;
+            }
+
+            let pos = body.get(&physic_world).position().translation.vector;
+            patroller.last_update += update_time.0;
+
+            while patroller.last_update >= 0.0 {
+                patroller.last_update -= ::CONFIG.patroller_update_time;
+
+                let seen = ::util::first_collided_on_ray(
+                    pos,
+                    player_pos,
+                    ::entity::ATTRACTED_VISION_GROUP,
+                    &[::entity::PLAYER_GROUP, ::entity::WALL_GROUP],
+                    &physic_world,
+                    &mut self.collided,
+                );
+
+                if seen.iter().any(|&e| players.get(e).is_some()) {
+                    patroller.chasing = true;
+                } else if patroller.chasing {
+                    patroller.chasing = false;
+                    patroller.rejoin_route(pos);
+                }
+            }
+
+            if patroller.chasing {
+                momentum.direction = (player_pos - pos).normalize();
+            } else {
+                let waypoint = patroller.route[patroller.next];
+                if (waypoint - pos).norm() < ::CONFIG.patroller_waypoint_distance {
+                    patroller.next = (patroller.next + 1) % patroller.route.len();
+                    patroller.goal = None;
+                }
+
+                let waypoint = patroller.route[patroller.next];
+                let recompute_goal = patroller.goal
+                    .map(|goal| (goal - pos).norm() < ::CONFIG.patroller_waypoint_distance)
+                    .unwrap_or(true);
+
+                if recompute_goal {
+                    patroller.goal = maze.find_path(pos, waypoint)
+                        .and_then(|path| path.get(1).cloned());
+                }
+
+                momentum.direction = (patroller.goal.unwrap_or(waypoint) - pos).normalize();
+            }
+
+            // Directions toward the entity position itself are NaN
+            if momentum.direction.iter().any(|c| c.is_nan()) {
+                momentum.direction = ::na::zero();
+            }
+        }
+    }
+}
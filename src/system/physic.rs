@@ -3,6 +3,27 @@ use ncollide::events::ProximityEvent;
 use ncollide::query::Proximity;
 use specs::Join;
 
+/// Distance from the floor under which the player can jump
+const JUMP_FLOOR_TOLERANCE: f32 = 0.01;
+
+/// Whether a short ray cast downward from the center of the player hits the floor
+fn on_floor(position: ::na::Vector3<f32>, physic_world: &::resource::PhysicWorld) -> bool {
+    let ray = ::ncollide::query::Ray {
+        origin: ::na::Point3::from_coordinates(position),
+        dir: -::na::Vector3::z(),
+    };
+
+    let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
+    group.set_membership(&[::entity::PLAYER_GROUP]);
+    group.set_whitelist(&[::entity::FLOOR_CEIL_GROUP]);
+
+    // The cylinder of the player has its axis along y, its vertical half extent is its radius
+    physic_world
+        .collision_world()
+        .interferences_with_ray(&ray, &group.as_collision_groups())
+        .any(|(_, collision)| collision.toi <= ::CONFIG.player_radius + JUMP_FLOOR_TOLERANCE)
+}
+
 pub struct PhysicSystem;
 
 impl<'a> ::specs::System<'a> for PhysicSystem {
@@ -14,7 +35,9 @@ impl<'a> ::specs::System<'a> for PhysicSystem {
         ::specs::WriteStorage<'a, ::component::Contactor>,
         ::specs::WriteStorage<'a, ::component::Proximitor>,
         ::specs::Fetch<'a, ::resource::UpdateTime>,
+        ::specs::Fetch<'a, ::resource::PlayerControl>,
         ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+        ::specs::FetchMut<'a, ::resource::Interpolation>,
         ::specs::Entities<'a>,
     );
 
@@ -28,12 +51,22 @@ impl<'a> ::specs::System<'a> for PhysicSystem {
             mut contactors,
             mut proximitors,
             update_time,
+            player_control,
             mut physic_world,
+            mut interpolation,
             entities,
         ): Self::SystemData,
     ) {
+        interpolation.previous.clear();
+        for (_, body, entity) in (&momentums, &bodies, &*entities).join() {
+            interpolation.previous.insert(entity, body.get(&physic_world).position().clone());
+        }
+
         // TODO: use integrator to modify rigidbody
         for (momentum, body, entity) in (&momentums, &mut bodies, &*entities).join() {
+            let can_jump = players.get(entity).is_some() && player_control.can_jump;
+            let grounded = can_jump
+                && on_floor(body.get(&physic_world).position().translation.vector, &physic_world);
             let body = body.get_mut(&mut physic_world);
             let lin_vel = body.lin_vel();
             let ang_vel = body.ang_vel();
@@ -42,14 +75,24 @@ impl<'a> ::specs::System<'a> for PhysicSystem {
 
             body.append_lin_force(-momentum.damping * lin_vel);
 
-            if players.get(entity).is_some() {
+            if players.get(entity).is_some() && !player_control.zero_gravity {
                 body.append_lin_force(
                     ::CONFIG.player_gravity * ::na::Vector3::new(0.0, 0.0, -1.0),
                 );
             }
 
+            // Jump from the floor, falling back with its own gravity
+            if can_jump {
+                if player_control.jump && grounded && lin_vel[2] <= 0.0 {
+                    body.set_lin_vel(::na::Vector3::new(lin_vel[0], lin_vel[1], ::CONFIG.player_jump_velocity));
+                }
+                body.append_lin_force(
+                    ::CONFIG.player_jump_gravity / body.inv_mass() * ::na::Vector3::new(0.0, 0.0, -1.0),
+                );
+            }
+
             if let Some(ref hook) = hooks.get(entity) {
-                if let Some(ref anchor) = hook.anchor {
+                if let (&Some(ref anchor), ::component::HookMode::Pull) = (&hook.anchor, hook.mode) {
                     let dir = (anchor.pos - body.position().translation.vector).normalize();
                     body.append_lin_force(hook.force * dir);
                 }
@@ -76,11 +119,29 @@ impl<'a> ::specs::System<'a> for PhysicSystem {
             proximitor.intersections.clear();
         }
 
-        let mut remaining_to_update = update_time.0;
-        while remaining_to_update > ::CONFIG.physic_min_step_time {
-            let step = remaining_to_update.min(::CONFIG.physic_max_step_time);
-            remaining_to_update -= step;
-            physic_world.step(step);
+        if update_time.0 > 0.0 {
+            physic_world.step(update_time.0);
+
+            // Rope constraint: keep hooked bodies within rope length of the anchor
+            for (hook, body) in (&hooks, &mut bodies).join() {
+                if let (&Some(ref anchor), Some(length)) = (&hook.anchor, hook.length) {
+                    let body = body.get_mut(&mut physic_world);
+                    let delta = body.position().translation.vector - anchor.pos;
+                    let distance = delta.norm();
+                    if distance > length {
+                        let normal = delta / distance;
+                        let mut position = body.position().clone();
+                        position.translation.vector = anchor.pos + normal * length;
+                        body.set_transformation(position);
+
+                        let lin_vel = body.lin_vel();
+                        let radial_vel = lin_vel.dot(&normal);
+                        if radial_vel > 0.0 {
+                            body.set_lin_vel(lin_vel - radial_vel * normal);
+                        }
+                    }
+                }
+            }
 
             for (co1, co2, mut contact) in physic_world.collision_world().contacts() {
                 let (entity_1, entity_2) = match (&co1.data, &co2.data) {
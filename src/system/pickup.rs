@@ -0,0 +1,90 @@
+use specs::Join;
+use component::PickupEffect;
+
+pub struct PickupSystem;
+
+impl<'a> ::specs::System<'a> for PickupSystem {
+    type SystemData = (
+        ::specs::ReadStorage<'a, ::component::Pickup>,
+        ::specs::ReadStorage<'a, ::component::Proximitor>,
+        ::specs::ReadStorage<'a, ::component::Player>,
+        ::specs::WriteStorage<'a, ::component::Shooter>,
+        ::specs::WriteStorage<'a, ::component::WeaponAnimation>,
+        ::specs::WriteStorage<'a, ::component::PhysicSensor>,
+        ::specs::WriteStorage<'a, ::component::DynamicHud>,
+        ::specs::WriteStorage<'a, ::component::DynamicGraphicsAssets>,
+        ::specs::Fetch<'a, ::resource::TimeScale>,
+        ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::FetchMut<'a, ::resource::PowerUps>,
+        ::specs::FetchMut<'a, ::resource::Keys>,
+        ::specs::FetchMut<'a, ::resource::PhysicWorld>,
+        ::specs::Entities<'a>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            pickups,
+            proximitors,
+            players,
+            mut shooters,
+            mut weapon_animations,
+            mut sensors,
+            mut dynamic_huds,
+            mut dynamic_graphics_assets,
+            time_scale,
+            audio,
+            mut power_ups,
+            mut keys,
+            mut physic_world,
+            entities,
+        ): Self::SystemData,
+    ) {
+        // Power-ups last in wall-clock time whatever the time scale
+        let real_time = time_scale.real_time;
+        power_ups.speed_boost = (power_ups.speed_boost - real_time).max(0.0);
+        power_ups.slow_motion = (power_ups.slow_motion - real_time).max(0.0);
+        power_ups.invulnerability = (power_ups.invulnerability - real_time).max(0.0);
+
+        for (pickup, proximitor, sensor, entity) in (&pickups, &proximitors, &mut sensors, &*entities).join() {
+            if proximitor.intersections.is_empty() {
+                continue;
+            }
+
+            match pickup.effect {
+                PickupEffect::Reload => {
+                    for (_, shooter) in (&players, &mut shooters).join() {
+                        shooter.bullets = shooter.weapon.bullets;
+                        shooter.timer = 0.0;
+                    }
+                }
+                PickupEffect::ExtraBullet => {
+                    for (_, shooter, animation) in (&players, &mut shooters, &mut weapon_animations).join() {
+                        shooter.weapon.bullets += 1;
+                        shooter.bullets += 1;
+                        if ::CONFIG.player_show_weapon {
+                            let bullet = ::entity::create_weapon_bullet(
+                                shooter.weapon.bullets - 1,
+                                shooter.weapon.bullet_color(),
+                                &mut dynamic_huds,
+                                &mut dynamic_graphics_assets,
+                                &entities,
+                            );
+                            animation.bullets.insert(0, bullet);
+                        }
+                    }
+                }
+                PickupEffect::SpeedBoost => power_ups.speed_boost = ::CONFIG.pickup_speed_boost_duration,
+                PickupEffect::SlowMotion => power_ups.slow_motion = ::CONFIG.pickup_slow_motion_duration,
+                PickupEffect::Invulnerability => power_ups.invulnerability = ::CONFIG.pickup_invulnerability_duration,
+                PickupEffect::Key(key) => {
+                    keys.0.insert(key);
+                }
+            }
+
+            audio.play_unspatial(::audio::Sound::Pickup);
+            sensor.remove(&mut physic_world);
+            entities.delete(entity).unwrap();
+        }
+    }
+}
@@ -10,9 +10,18 @@ impl<'a> ::specs::System<'a> for PlayerControlSystem {
         ::specs::WriteStorage<'a, ::component::Aim>,
         ::specs::WriteStorage<'a, ::component::Shooter>,
         ::specs::WriteStorage<'a, ::component::Momentum>,
+        ::specs::WriteStorage<'a, ::component::Hook>,
+        ::specs::ReadStorage<'a, ::component::PhysicBody>,
         ::specs::Fetch<'a, ::resource::Events>,
         ::specs::Fetch<'a, ::resource::Save>,
+        ::specs::Fetch<'a, ::resource::PlayerEffects>,
+        ::specs::Fetch<'a, ::resource::PowerUps>,
+        ::specs::Fetch<'a, ::resource::UpdateTime>,
+        ::specs::Fetch<'a, ::resource::PhysicWorld>,
         ::specs::FetchMut<'a, ::resource::PlayerControl>,
+        ::specs::FetchMut<'a, ::resource::Replays>,
+        ::specs::FetchMut<'a, ::resource::Stats>,
+        ::specs::Entities<'a>,
     );
 
     fn run(
@@ -22,21 +31,40 @@ impl<'a> ::specs::System<'a> for PlayerControlSystem {
             mut aims,
             mut shooters,
             mut momentums,
+            mut hooks,
+            bodies,
             events,
             save,
+            player_effects,
+            power_ups,
+            update_time,
+            physic_world,
             mut player_control,
+            mut replays,
+            mut stats,
+            entities,
         ): Self::SystemData,
     ) {
-        let (_, player_aim, player_shooter, player_momentum) = (
+        let (_, player_aim, player_shooter, player_momentum, player_body, player_entity) = (
             &players,
             &mut aims,
             &mut shooters,
             &mut momentums,
+            &bodies,
+            &*entities,
         ).join()
             .next()
             .unwrap();
 
-        let mut inputs = vec![];
+        player_control.dash_timer = (player_control.dash_timer - update_time.0).max(0.0);
+        player_control.dash_cooldown = (player_control.dash_cooldown - update_time.0).max(0.0);
+
+        let player_pos = player_body.get(&physic_world).position().translation.vector;
+        stats.travel(player_pos);
+        let mut tick = ::replay::ReplayTick {
+            position: [player_pos[0], player_pos[1], player_pos[2]],
+            ..Default::default()
+        };
         for ev in events.0.iter() {
             match *ev {
                 Event::WindowEvent {
@@ -48,7 +76,7 @@ impl<'a> ::specs::System<'a> for PlayerControlSystem {
                         },
                     ..
                 } => {
-                    inputs.extend(save.convert_mouse_button_input(button).iter().map(|b| (b.clone(), state.clone())));
+                    tick.inputs.extend(save.convert_mouse_button_input(button).iter().map(|b| (b.clone(), state == ElementState::Pressed)));
                 }
                 Event::WindowEvent {
                     event: WindowEvent::KeyboardInput {
@@ -61,7 +89,7 @@ impl<'a> ::specs::System<'a> for PlayerControlSystem {
                     },
                     ..
                 } => {
-                    inputs.extend(save.convert_keycode_input(keycode).iter().map(|c| (c.clone(), state.clone())));
+                    tick.inputs.extend(save.convert_keycode_input(keycode).iter().map(|c| (c.clone(), state == ElementState::Pressed)));
                 }
                 Event::DeviceEvent {
                     event: DeviceEvent::MouseMotion {
@@ -69,32 +97,75 @@ impl<'a> ::specs::System<'a> for PlayerControlSystem {
                     },
                     ..
                 } => {
-                    player_control.pointer[0] += dx as f32 * save.mouse_sensibility();
-                    player_control.pointer[1] += dy as f32 * save.mouse_sensibility();
-                    player_control.pointer[1] = player_control.pointer[1]
-                        .min(::std::f32::consts::FRAC_PI_2)
-                        .max(-::std::f32::consts::FRAC_PI_2);
+                    tick.motions.push((
+                        dx as f32 * save.mouse_sensibility(),
+                        dy as f32 * save.mouse_sensibility(),
+                    ));
                 }
                 _ => (),
             }
         }
 
-        for input in inputs {
+        // Inputs are recorded for replays, or replaced by the replayed ones
+        let tick = replays.tick(tick);
+
+        for &(dx, dy) in &tick.motions {
+            if player_control.zero_gravity {
+                // Rotate in the frame of the player so there is no gimbal lock
+                player_control.orientation = player_control.orientation
+                    * ::na::UnitQuaternion::new(::na::Vector3::new(0.0, 0.0, -dx))
+                    * ::na::UnitQuaternion::new(::na::Vector3::new(0.0, dy, 0.0));
+            }
+            player_control.pointer[0] += dx;
+            player_control.pointer[1] += dy;
+            player_control.pointer[1] = player_control.pointer[1]
+                .min(::std::f32::consts::FRAC_PI_2)
+                .max(-::std::f32::consts::FRAC_PI_2);
+        }
+
+        let mut dash = false;
+        for &(input, pressed) in &tick.inputs {
             match input {
-                (::resource::Input::Shoot, state) => player_shooter.set_shoot(state == ElementState::Released),
-                (::resource::Input::Direction(direction), state) => {
+                ::resource::Input::Shoot => player_shooter.set_trigger(pressed),
+                ::resource::Input::Direction(direction) => {
                     player_control.directions.retain(|&elt| elt != direction);
-                    if let ElementState::Pressed = state {
+                    if pressed {
                         player_control.directions.push(direction);
                     }
                 }
+                ::resource::Input::Hook => if let Some(hook) = hooks.get_mut(player_entity) {
+                    hook.set_launch(pressed);
+                },
+                ::resource::Input::ReelIn => if let Some(hook) = hooks.get_mut(player_entity) {
+                    hook.reel_in = pressed;
+                },
+                ::resource::Input::ReelOut => if let Some(hook) = hooks.get_mut(player_entity) {
+                    hook.reel_out = pressed;
+                },
+                ::resource::Input::RollLeft => player_control.roll_left = pressed,
+                ::resource::Input::RollRight => player_control.roll_right = pressed,
+                ::resource::Input::Dash => dash |= pressed,
+                ::resource::Input::Jump => player_control.jump = pressed,
             }
         }
 
-        player_aim.rotation = ::na::UnitQuaternion::from_rotation_matrix(
-            &(::na::Rotation3::new(::na::Vector3::new(0.0, 0.0, -player_control.pointer[0]))
-                * ::na::Rotation3::new(::na::Vector3::new(0.0, player_control.pointer[1], 0.0))),
-        );
+        if player_control.zero_gravity {
+            let mut roll = 0.0;
+            if player_control.roll_left {
+                roll -= ::CONFIG.player_roll_velocity * update_time.0;
+            }
+            if player_control.roll_right {
+                roll += ::CONFIG.player_roll_velocity * update_time.0;
+            }
+            player_control.orientation = player_control.orientation
+                * ::na::UnitQuaternion::new(::na::Vector3::new(roll, 0.0, 0.0));
+            player_aim.rotation = player_control.orientation;
+        } else {
+            player_aim.rotation = ::na::UnitQuaternion::from_rotation_matrix(
+                &(::na::Rotation3::new(::na::Vector3::new(0.0, 0.0, -player_control.pointer[0]))
+                    * ::na::Rotation3::new(::na::Vector3::new(0.0, player_control.pointer[1], 0.0))),
+            );
+        }
 
         let mut move_vector: ::na::Vector3<f32> = ::na::zero();
         if player_control.directions.is_empty() {
@@ -106,12 +177,41 @@ impl<'a> ::specs::System<'a> for PlayerControlSystem {
                     Direction::Backward => move_vector[0] = -1.0,
                     Direction::Left => move_vector[1] = 1.0,
                     Direction::Right => move_vector[1] = -1.0,
+                    Direction::Up => move_vector[2] = 1.0,
+                    Direction::Down => move_vector[2] = -1.0,
                 }
             }
-            move_vector =
-                (::na::Rotation3::new(::na::Vector3::new(0.0, 0.0, -player_control.pointer[0]))
-                    * move_vector)
-                    .normalize();
+            // Movement is relative to the aim in zero gravity and horizontal otherwise
+            if player_control.zero_gravity {
+                move_vector = player_control.orientation * move_vector;
+            } else {
+                move_vector[2] = 0.0;
+                move_vector = ::na::Rotation3::new(::na::Vector3::new(0.0, 0.0, -player_control.pointer[0]))
+                    * move_vector;
+            }
+            if move_vector.norm() != 0.0 {
+                move_vector = move_vector.normalize();
+            }
+            if player_effects.invert > 0.0 {
+                move_vector = -move_vector;
+            }
+            if player_effects.slow > 0.0 {
+                move_vector *= ::CONFIG.slow_effect_coef;
+            }
+            if power_ups.speed_boost > 0.0 {
+                move_vector *= ::CONFIG.pickup_speed_boost_coef;
+            }
+            // The player keeps its real speed during slow motion
+            if power_ups.slow_motion > 0.0 {
+                move_vector /= ::CONFIG.pickup_slow_motion_coef;
+            }
+            if dash && player_control.dash_cooldown == 0.0 && move_vector.norm() != 0.0 {
+                player_control.dash_timer = ::CONFIG.player_dash_duration;
+                player_control.dash_cooldown = ::CONFIG.player_dash_cooldown;
+            }
+            if player_control.dash_timer > 0.0 {
+                move_vector *= ::CONFIG.player_dash_coef;
+            }
             player_momentum.direction = move_vector;
         }
     }
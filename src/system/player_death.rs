@@ -6,15 +6,30 @@ impl<'a> ::specs::System<'a> for PlayerDeathSystem {
     type SystemData = (
         ::specs::ReadStorage<'a, ::component::Proximitor>,
         ::specs::ReadStorage<'a, ::component::Player>,
-        ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::ReadStorage<'a, ::component::Hazard>,
+        ::specs::Fetch<'a, ::resource::PowerUps>,
         ::specs::FetchMut<'a, ::resource::LevelActions>,
+        ::specs::FetchMut<'a, ::resource::GameEvents>,
     );
 
-    fn run(&mut self, (proximitors, players, audio, mut level_actions): Self::SystemData) {
+    fn run(&mut self, (proximitors, players, hazards, power_ups, mut level_actions, mut game_events): Self::SystemData) {
+        if power_ups.invulnerability > 0.0 {
+            return;
+        }
+
         for (_, proximitor) in (&players, &proximitors).join() {
-            if !proximitor.intersections.is_empty() {
-                audio.play_unspatial(::audio::Sound::Death);
+            let cause = proximitor.intersections
+                .iter()
+                .filter_map(|&entity| match hazards.get(entity) {
+                    Some(hazard) if hazard.active => Some(::resource::DeathCause::Hazard),
+                    Some(_) => None,
+                    None => Some(::resource::DeathCause::Enemy),
+                })
+                .next();
+
+            if let Some(cause) = cause {
                 level_actions.0.push(::resource::LevelAction::Reset);
+                game_events.0.push(::resource::GameEvent::PlayerDied { cause });
             }
         }
     }
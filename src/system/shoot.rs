@@ -22,7 +22,8 @@ impl<'a> ::specs::System<'a> for ShootSystem {
         ::specs::WriteStorage<'a, ::component::DynamicDraw>,
         ::specs::Fetch<'a, ::resource::PhysicWorld>,
         ::specs::Fetch<'a, ::resource::UpdateTime>,
-        ::specs::Fetch<'a, ::resource::Audio>,
+        ::specs::FetchMut<'a, ::resource::TimeScale>,
+        ::specs::FetchMut<'a, ::resource::GameEvents>,
         ::specs::Entities<'a>,
     );
 
@@ -39,94 +40,147 @@ impl<'a> ::specs::System<'a> for ShootSystem {
             mut dynamic_draws,
             physic_world,
             update_time,
-            audio,
+            mut time_scale,
+            mut game_events,
             entities,
         ): Self::SystemData,
     ) {
         for (aim, animation, body, shooter, entity) in
             (&aims, &animations, &bodies, &mut shooters, &*entities).join()
         {
+            // Charge
+            if shooter.trigger {
+                shooter.charge += update_time.0;
+            }
+
             // Reload
-            if shooter.bullets != shooter.max_bullets {
-                shooter.timer += update_time.0;
-                if shooter.timer >= shooter.reload_time {
-                    shooter.bullets += 1;
-                    shooter.timer = 0.0;
-                    dynamic_assets
-                        .get_mut(animation.bullets[shooter.bullets - 1])
-                        .unwrap()
-                        .color = ::CONFIG.weapon_bullet_color;
+            match shooter.weapon.reload_mode {
+                ::component::ReloadMode::PerBullet => {
+                    if shooter.bullets != shooter.weapon.bullets && shooter.can_reload() {
+                        shooter.timer += update_time.0;
+                        if shooter.timer >= shooter.weapon.reload_time {
+                            shooter.bullets += 1;
+                            shooter.ammo = shooter.ammo.map(|a| a - 1);
+                            shooter.timer = 0.0;
+                        }
+                    }
+                }
+                ::component::ReloadMode::Magazine => {
+                    if shooter.bullets == 0 && shooter.can_reload() {
+                        shooter.timer += update_time.0;
+                        if shooter.timer >= shooter.weapon.reload_time {
+                            shooter.bullets = shooter.ammo
+                                .map(|a| a.min(shooter.weapon.bullets))
+                                .unwrap_or(shooter.weapon.bullets);
+                            shooter.ammo = shooter.ammo.map(|a| a - shooter.bullets);
+                            shooter.timer = 0.0;
+                        }
+                    }
                 }
             }
 
-            // Shoot
-            if shooter.shoot && shooter.bullets > 0 {
-                shooter.bullets -= 1;
+            // Trigger
+            if shooter.shoot && shooter.bullets > 0 && shooter.burst == 0 {
                 shooter.shoot = false;
-                dynamic_assets
-                    .get_mut(animation.bullets[shooter.bullets])
-                    .unwrap()
-                    .color = ::CONFIG.weapon_bullet_empty_color;
+                shooter.burst = shooter.weapon.burst;
+                shooter.burst_timer = 0.0;
+            }
 
-                let body_pos = body.get(&physic_world).position().clone();
+            // Shoot
+            shooter.burst_timer = (shooter.burst_timer - update_time.0).max(0.0);
+            if shooter.burst > 0 && shooter.burst_timer <= 0.0 {
+                if shooter.bullets == 0 {
+                    shooter.burst = 0;
+                } else {
+                    shooter.bullets -= 1;
+                    shooter.burst -= 1;
+                    shooter.burst_timer = shooter.weapon.burst_interval;
 
-                let ray = ::ncollide::query::Ray {
-                    origin: ::na::Point3::from_coordinates(body_pos.translation.vector),
-                    dir: aim.rotation * ::na::Vector3::x(),
-                };
+                    let body_pos = body.get(&physic_world).position().clone();
+                    let ray_draw_origin = (body_pos.translation * aim.rotation * animation.weapon_trans
+                        * animation.shoot_pos)
+                        .coords;
 
-                // TODO: resolve hack with membership nphysic #82
-                let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
-                group.set_membership(&[::entity::PLAYER_LASER_GROUP]);
-                group.set_whitelist(&[::entity::MONSTER_GROUP, ::entity::WALL_GROUP]);
+                    let mut killed = false;
+                    for i in 0..shooter.weapon.rays {
+                        let angle = if shooter.weapon.rays > 1 {
+                            shooter.weapon.spread_angle * (i as f32 / (shooter.weapon.rays - 1) as f32 - 0.5)
+                        } else {
+                            0.0
+                        };
 
-                self.collided.clear();
-                for (other_body, collision) in physic_world
-                    .collision_world()
-                    .interferences_with_ray(&ray, &group.as_collision_groups())
-                {
-                    if let ::nphysics::object::WorldObject::RigidBody(other_body) = other_body.data
-                    {
-                        let other_entity =
-                            ::component::PhysicBody::entity(physic_world.rigid_body(other_body));
-                        if entity != other_entity {
-                            self.collided.push((other_entity, collision.toi));
+                        let ray = ::ncollide::query::Ray {
+                            origin: ::na::Point3::from_coordinates(body_pos.translation.vector),
+                            dir: aim.rotation * ::na::Rotation3::new(::na::Vector3::new(0.0, 0.0, angle)) * ::na::Vector3::x(),
+                        };
+
+                        // TODO: resolve hack with membership nphysic #82
+                        let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
+                        group.set_membership(&[::entity::PLAYER_LASER_GROUP]);
+                        group.set_whitelist(&[::entity::MONSTER_GROUP, ::entity::WALL_GROUP]);
+
+                        self.collided.clear();
+                        for (other_body, collision) in physic_world
+                            .collision_world()
+                            .interferences_with_ray(&ray, &group.as_collision_groups())
+                        {
+                            if let ::nphysics::object::WorldObject::RigidBody(other_body) = other_body.data
+                            {
+                                let other_entity =
+                                    ::component::PhysicBody::entity(physic_world.rigid_body(other_body));
+                                if entity != other_entity {
+                                    self.collided.push((other_entity, collision.toi));
+                                }
+                            }
                         }
+                        self.collided
+                            .sort_by(|a, b| (a.1).partial_cmp(&b.1).unwrap());
+                        let mut size = 1000.0; // infinite
+                        for collided in &self.collided {
+                            if let Some(ref mut life) = lifes.get_mut(collided.0) {
+                                life.kill();
+                                killed = true;
+                                if !shooter.piercing() {
+                                    size = collided.1;
+                                    break;
+                                }
+                            } else {
+                                size = collided.1;
+                                break;
+                            }
+                        }
+
+                        let ray_draw_end = (ray.origin + size * ray.dir).coords;
+
+                        ::entity::create_light_ray(
+                            ray_draw_origin,
+                            ray_draw_end,
+                            animation.light_ray_radius,
+                            &mut reducers,
+                            &mut dynamic_draws,
+                            &mut dynamic_assets,
+                            &entities,
+                        );
                     }
-                }
-                self.collided
-                    .sort_by(|a, b| (a.1).partial_cmp(&b.1).unwrap());
-                let mut size = 1000.0; // infinite
-                let mut killed = false;
-                for collided in &self.collided {
-                    if let Some(ref mut life) = lifes.get_mut(collided.0) {
-                        life.kill();
-                        killed = true;
-                    } else {
-                        size = collided.1;
-                        break;
-                    }
-                }
 
-                audio.play_unspatial(::audio::Sound::Shoot);
-                if killed {
-                    audio.play_unspatial(::audio::Sound::Kill);
+                    game_events.0.push(::resource::GameEvent::ShotFired { hit: killed });
+                    if killed {
+                        time_scale.hit_stop();
+                    }
                 }
+            }
 
-                let ray_draw_origin = (body_pos.translation * aim.rotation * animation.weapon_trans
-                    * animation.shoot_pos)
-                    .coords;
-                let ray_draw_end = (ray.origin + size * ray.dir).coords;
-
-                ::entity::create_light_ray(
-                    ray_draw_origin,
-                    ray_draw_end,
-                    animation.light_ray_radius,
-                    &mut reducers,
-                    &mut dynamic_draws,
-                    &mut dynamic_assets,
-                    &entities,
-                );
+            // Animate
+            for (i, &bullet) in animation.bullets.iter().enumerate() {
+                dynamic_assets.get_mut(bullet).unwrap().color = if i >= shooter.bullets {
+                    ::CONFIG.weapon_bullet_empty_color
+                } else if i == shooter.bullets - 1 && shooter.trigger && shooter.charged()
+                    && shooter.weapon.charge_time.is_some()
+                {
+                    ::CONFIG.weapon_charged_bullet_color
+                } else {
+                    shooter.weapon.bullet_color()
+                };
             }
         }
     }
@@ -0,0 +1,21 @@
+pub struct StatsSystem;
+
+impl<'a> ::specs::System<'a> for StatsSystem {
+    type SystemData = (
+        ::specs::Fetch<'a, ::resource::GameEvents>,
+        ::specs::FetchMut<'a, ::resource::Stats>,
+    );
+
+    fn run(&mut self, (game_events, mut stats): Self::SystemData) {
+        use resource::GameEvent;
+        for event in &game_events.0 {
+            match *event {
+                GameEvent::ShotFired { hit } => stats.shot(hit),
+                GameEvent::EnemyKilled { kind, .. } => stats.kill(kind),
+                GameEvent::PlayerDied { .. } => stats.death(),
+                GameEvent::HookAttached => stats.hook(),
+                _ => (),
+            }
+        }
+    }
+}
@@ -1,17 +1,60 @@
 use specs::Join;
 
 pub struct TurretControlSystem {
-    shoots: Vec<(::na::Vector3<f32>, ::na::Vector3<f32>)>,
+    shoots: Vec<(::na::Vector3<f32>, ::na::Vector3<f32>, f32, ::component::ProjectileEffect)>,
+    collided: Vec<(::specs::Entity, f32)>,
 }
 
 impl TurretControlSystem {
     pub fn new() -> Self {
         TurretControlSystem {
             shoots: vec![],
+            collided: vec![],
         }
     }
 }
 
+/// Return the direction to shoot a projectile at velocity to intercept target moving at
+/// target_velocity. The anticipation is weighted by lead_factor.
+fn aim_direction(
+    relative_pos: ::na::Vector3<f32>,
+    target_velocity: ::na::Vector3<f32>,
+    velocity: f32,
+    lead_factor: f32,
+) -> ::na::Vector3<f32> {
+    if lead_factor == 0.0 {
+        return relative_pos;
+    }
+
+    // Solve |relative_pos + target_velocity * t| = velocity * t
+    let a = target_velocity.norm_squared() - velocity.powi(2);
+    let b = 2.0 * relative_pos.dot(&target_velocity);
+    let c = relative_pos.norm_squared();
+
+    let time = if a.abs() < ::std::f32::EPSILON {
+        if b < 0.0 { Some(-c / b) } else { None }
+    } else {
+        let delta = b.powi(2) - 4.0 * a * c;
+        if delta < 0.0 {
+            None
+        } else {
+            let t1 = (-b - delta.sqrt()) / (2.0 * a);
+            let t2 = (-b + delta.sqrt()) / (2.0 * a);
+            match (t1 > 0.0, t2 > 0.0) {
+                (true, true) => Some(t1.min(t2)),
+                (true, false) => Some(t1),
+                (false, true) => Some(t2),
+                (false, false) => None,
+            }
+        }
+    };
+
+    match time {
+        Some(time) => relative_pos + lead_factor * time * target_velocity,
+        None => relative_pos,
+    }
+}
+
 impl<'a> ::specs::System<'a> for TurretControlSystem {
     type SystemData = (
         ::specs::ReadStorage<'a, ::component::Player>,
@@ -47,20 +90,43 @@ impl<'a> ::specs::System<'a> for TurretControlSystem {
             entities,
         ): Self::SystemData,
     ) {
-        let player_pos = {
+        let (player_pos, player_vel) = {
             let (_, player_body) = (&players, &bodies).join().next().unwrap();
-            player_body.get(&physic_world).position().clone()
+            let player_body = player_body.get(&physic_world);
+            (player_body.position().translation.vector, player_body.lin_vel())
         };
 
         for (turret, body) in (&mut turrets, &mut bodies).join() {
-            turret.last_shoot += update_time.0;
-            let direction = player_pos.translation.vector - turret.position;
+            let relative_pos = player_pos - turret.position;
 
+            let in_range = turret.range.map(|range| relative_pos.norm() <= range).unwrap_or(true);
+            let in_sight = in_range && ::util::first_collided_on_ray(
+                turret.position,
+                player_pos,
+                ::entity::ATTRACTED_VISION_GROUP,
+                &[::entity::PLAYER_GROUP, ::entity::WALL_GROUP],
+                &physic_world,
+                &mut self.collided,
+            ).iter().any(|&e| players.get(e).is_some());
+
+            if !in_sight {
+                // Ready to shoot as soon as player is seen
+                turret.last_shoot = (turret.last_shoot + update_time.0).min(turret.reload_time);
+                continue;
+            }
+
+            turret.last_shoot += update_time.0;
+            let direction = aim_direction(
+                relative_pos,
+                player_vel,
+                turret.projectile_velocity,
+                turret.lead_factor,
+            );
 
-            while turret.last_shoot > turret.reload_time {
+            while turret.last_shoot >= turret.reload_time {
                 audio.play(::audio::Sound::DepthBallBirthDeath, turret.position.into());
                 turret.last_shoot -= turret.reload_time;
-                self.shoots.push((turret.position, direction));
+                self.shoots.push((turret.position, direction, turret.projectile_velocity, turret.effect));
             }
 
             let rotation = ::na::UnitQuaternion::rotation_between(
@@ -75,10 +141,12 @@ impl<'a> ::specs::System<'a> for TurretControlSystem {
             body.get_mut(&mut physic_world).set_transformation(trans);
         }
 
-        for (pos, dir) in self.shoots.drain(..) {
+        for (pos, dir, velocity, effect) in self.shoots.drain(..) {
             ::entity::create_depth_ball(
                 pos,
                 dir,
+                velocity,
+                effect,
                 &mut momentums,
                 &mut depth_balls,
                 &mut contactors,
@@ -12,6 +12,8 @@ impl<'a> ::specs::System<'a> for UpdateDynamicDrawEraserSystem {
         ::specs::WriteStorage<'a, ::component::DynamicDraw>,
         ::specs::WriteStorage<'a, ::component::Reducer>,
         ::specs::Fetch<'a, ::resource::PhysicWorld>,
+        ::specs::Fetch<'a, ::resource::Interpolation>,
+        ::specs::Entities<'a>,
     );
 
     fn run(
@@ -24,19 +26,23 @@ impl<'a> ::specs::System<'a> for UpdateDynamicDrawEraserSystem {
             mut dynamic_draws,
             mut reducers,
             physic_world,
+            interpolation,
+            entities,
         ): Self::SystemData,
     ) {
-        for (assets, body) in (&mut dynamic_graphics_assets, &bodies).join() {
-            let mut trans = body.get(&physic_world).position() * assets.primitive_trans;
+        for (assets, body, entity) in (&mut dynamic_graphics_assets, &bodies, &*entities).join() {
+            let position = interpolation.position(entity, body.get(&physic_world).position());
+            let mut trans = position * assets.primitive_trans;
             assets.world_trans = ::graphics::shader::draw1_vs::ty::World {
                 world: trans.unwrap().into(),
             }
         }
 
-        for (hook, body, aim) in (&hooks, &bodies, &aims).join() {
+        for (hook, body, aim, entity) in (&hooks, &bodies, &aims, &*entities).join() {
             if let Some(ref anchor) = hook.anchor {
                 let body_hook_local_pos = ::na::Vector3::new(0.0, 0.2, -0.2);
-                let hook_body_pos = body.get(&physic_world).position().translation.vector + aim.rotation*body_hook_local_pos;
+                let position = interpolation.position(entity, body.get(&physic_world).position());
+                let hook_body_pos = position.translation.vector + aim.rotation*body_hook_local_pos;
                 let aimto = hook_body_pos - anchor.pos;
 
                 let assets = dynamic_graphics_assets.get_mut(hook.draw).unwrap();
@@ -1,15 +1,20 @@
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::hash::Hash;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
 use std::f32::consts::{FRAC_PI_2, PI};
 use std::time::{Duration, Instant};
 use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+use rand::{Rng, SeedableRng, XorShiftRng};
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Direction {
     Forward,
     Backward,
     Left,
     Right,
+    Up,
+    Down,
 }
 
 impl Direction {
@@ -21,7 +26,11 @@ impl Direction {
             (Forward, Forward)
             | (Forward, Backward)
             | (Backward, Forward)
-            | (Backward, Backward) => false,
+            | (Backward, Backward)
+            | (Up, Up)
+            | (Up, Down)
+            | (Down, Up)
+            | (Down, Down) => false,
             _ => true,
         }
     }
@@ -33,6 +42,36 @@ impl Direction {
     }
 }
 
+/// Hash collections with a fixed hasher, so their iteration order only depends on insertions
+pub type FixedHashMap<K, V> = HashMap<K, V, BuildHasherDefault<DefaultHasher>>;
+pub type FixedHashSet<T> = HashSet<T, BuildHasherDefault<DefaultHasher>>;
+
+thread_local! {
+    // Each thread has its own generator so that runs verified in the background don't draw
+    // from the one of the game
+    static RNG: &'static Mutex<XorShiftRng> = Box::leak(Box::new(Mutex::new(XorShiftRng::new_unseeded())));
+}
+
+/// Random generator of the game, seeded at each level so that runs can be reproduced
+///
+/// The guard must be dropped before any other call to rng
+pub fn rng() -> MutexGuard<'static, XorShiftRng> {
+    // A verification that panicked doesn't poison the generator for the next ones, it is seeded
+    // again at each level anyway
+    RNG.with(|rng| *rng).lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn seed_rng(seed: u32) {
+    // Xorshift must not be seeded with zeros only
+    *rng() = XorShiftRng::from_seed([seed, 0x193a_6754, 0xa8a7_d469, 0x9783_0e05]);
+}
+
+/// Same as `::na::Vector3::new_random` using the game random generator
+pub fn random_vector3() -> ::na::Vector3<f32> {
+    let mut rng = rng();
+    ::na::Vector3::new(rng.gen(), rng.gen(), rng.gen())
+}
+
 pub fn high_byte(b: u32) -> u32 {
     b >> 8 as u8 as u32
 }
@@ -41,12 +80,16 @@ pub fn low_byte(b: u32) -> u32 {
     b as u8 as u32
 }
 
+pub fn duration_from_secs(secs: f32) -> Duration {
+    Duration::new(secs as u64, (secs.fract() * 1_000_000_000.0) as u32)
+}
+
 pub trait Pop {
     type Item;
     fn pop(&mut self) -> Option<Self::Item>;
 }
 
-impl<T: Eq + Hash + Clone> Pop for HashSet<T> {
+impl<T: Eq + Hash + Clone, S: BuildHasher> Pop for HashSet<T, S> {
     type Item = T;
     fn pop(&mut self) -> Option<Self::Item> {
         self.iter()
@@ -86,6 +129,41 @@ impl ConvCoord for ::na::Vector3<isize> {
     }
 }
 
+/// Cast a ray from origin to target and return the first entity hit
+///
+/// collided is a buffer given to avoid allocation
+pub fn first_collided_on_ray(
+    origin: ::na::Vector3<f32>,
+    target: ::na::Vector3<f32>,
+    membership: usize,
+    whitelist: &[usize],
+    physic_world: &::resource::PhysicWorld,
+    collided: &mut Vec<(::specs::Entity, f32)>,
+) -> Option<::specs::Entity> {
+    let ray = ::ncollide::query::Ray {
+        origin: ::na::Point3::from_coordinates(origin),
+        dir: target - origin,
+    };
+
+    let mut group = ::nphysics::object::RigidBodyCollisionGroups::new_dynamic();
+    group.set_membership(&[membership]);
+    group.set_whitelist(whitelist);
+
+    collided.clear();
+    for (other_body, collision) in physic_world
+        .collision_world()
+        .interferences_with_ray(&ray, &group.as_collision_groups())
+    {
+        if let ::nphysics::object::WorldObject::RigidBody(other_body) = other_body.data
+        {
+            let other_entity = ::component::PhysicBody::entity(physic_world.rigid_body(other_body));
+            collided.push((other_entity, collision.toi));
+        }
+    }
+    collided.sort_by(|a, b| (a.1).partial_cmp(&b.1).unwrap());
+    collided.first().map(|&(e, _)| e)
+}
+
 const BENCHMARKER_VECDEQUE_SIZE: usize = 60;
 
 pub struct Benchmark {
@@ -0,0 +1,158 @@
+use resource::{LevelAction, Replays};
+use replay::Replay;
+use stats::RunStats;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, Receiver, SendError, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// Run recorded in the game once verified
+pub struct VerifiedRun {
+    pub replay: Replay,
+    pub stats: RunStats,
+    pub result: Result<(), String>,
+}
+
+/// Verify the recorded runs in a background thread so that the game goes on during the
+/// simulation
+pub struct Verifier {
+    sender: Mutex<Sender<(Replay, RunStats)>>,
+    receiver: Mutex<Receiver<VerifiedRun>>,
+    /// Runs that couldn't be sent to the verification thread
+    unsent: Vec<VerifiedRun>,
+    pending: usize,
+    /// Reason of the last rejection and remaining duration of its notification
+    pub rejection: Option<(String, f32)>,
+}
+
+impl Verifier {
+    pub fn new(graphics: ::resource::Graphics) -> Self {
+        let (sender, runs) = channel::<(Replay, RunStats)>();
+        let (verified, receiver) = channel();
+        // Same stack size as the game thread
+        thread::Builder::new().stack_size(32 * 1024 * 1024).spawn(move || {
+            for (replay, stats) in runs {
+                // A panic in the simulation rejects the run and the thread goes on with the next
+                let result = panic::catch_unwind(AssertUnwindSafe(|| verify(&replay, &graphics)))
+                    .unwrap_or_else(|_| Err("the simulation panicked".to_string()));
+                if verified.send(VerifiedRun { replay, stats, result }).is_err() {
+                    break;
+                }
+            }
+        }).unwrap();
+
+        Verifier {
+            sender: Mutex::new(sender),
+            receiver: Mutex::new(receiver),
+            unsent: vec![],
+            pending: 0,
+            rejection: None,
+        }
+    }
+
+    /// The run is rejected if the verification thread has stopped
+    pub fn verify(&mut self, replay: Replay, stats: RunStats) {
+        if let Err(SendError((replay, stats))) = self.sender.lock().unwrap().send((replay, stats)) {
+            self.unsent.push(VerifiedRun {
+                replay,
+                stats,
+                result: Err("the verification thread has stopped".to_string()),
+            });
+        }
+        self.pending += 1;
+    }
+
+    /// Runs verified since the last call
+    pub fn verified_runs(&mut self) -> Vec<VerifiedRun> {
+        let mut runs = ::std::mem::replace(&mut self.unsent, vec![]);
+        runs.extend(self.receiver.lock().unwrap().try_iter());
+        self.pending -= runs.len();
+        runs
+    }
+
+    /// Number of runs being verified
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+}
+
+/// Simulate the recorded run again without drawing nor playing sounds and check that it
+/// completes the level in the claimed duration
+///
+/// Graphics are only used to create the static draws of the level.
+pub fn verify(replay: &Replay, graphics: &::resource::Graphics) -> Result<(), String> {
+    let parts = ::CONFIG.levels.get(replay.level)
+        .map(|level| &level.parts)
+        .ok_or_else(|| format!("unknown level {}", replay.level))?;
+    if replay.parts.len() != parts.len() {
+        return Err(format!("{} parts recorded instead of {}", replay.parts.len(), parts.len()));
+    }
+
+    let mut world = ::specs::World::new();
+    ::register_components(&mut world);
+    world.add_resource(graphics.clone());
+    world.add_resource(::resource::Events(vec![]));
+    world.add_resource(::resource::Save::default());
+    world.add_resource(::resource::PlayerControl::new());
+    world.add_resource(::resource::UpdateTime(0.0));
+    world.add_resource(::resource::TimeScale::new());
+    world.add_resource(::resource::GameDuration(Duration::new(0, 0)));
+    world.add_resource(::resource::LevelSeed(0));
+    world.add_resource(::resource::Interpolation::new());
+    world.add_resource(Replays::from_replay(replay.clone()));
+    world.add_resource(::resource::Stats::new());
+    world.add_resource(::resource::Activated(false));
+    world.add_resource(::resource::Audio::mute());
+    world.add_resource(::resource::LevelActions(vec![]));
+    world.add_resource(::resource::GameEvents(vec![]));
+    world.add_resource(::resource::GeneratorSpawns(vec![]));
+    world.add_resource(::resource::ErasedStatus::new());
+    world.maintain();
+
+    let mut dispatcher = ::game_update_dispatcher(true);
+    let update_step_duration = Duration::new(
+        0,
+        (::CONFIG.update_step_time * 1_000_000_000.0) as u32,
+    );
+
+    for (part, level) in parts.iter().enumerate() {
+        let seed = {
+            let mut player_control = world.write_resource::<::resource::PlayerControl>();
+            world.write_resource::<Replays>().start_part(part, &mut player_control.directions)
+        };
+        ::system::reset_level(&mut world, seed);
+        level.create(&mut world);
+        world.maintain();
+
+        // Same update as the game loop, the part must end with its last recorded update
+        let ticks = world.read_resource::<Replays>().part_ticks(part);
+        if ticks == 0 {
+            return Err(format!("part {} has no update", part));
+        }
+        for tick in 0..ticks {
+            world.write_resource::<::resource::GameDuration>().0 += update_step_duration;
+            let update_time = {
+                let slow_motion = world.read_resource::<::resource::PowerUps>().slow_motion > 0.0;
+                world.write_resource::<::resource::TimeScale>().update(::CONFIG.update_step_time, replay.game_speed, slow_motion)
+            };
+            world.write_resource::<::resource::UpdateTime>().0 = update_time;
+            dispatcher.dispatch(&mut world.res);
+            world.maintain();
+            world.write_resource::<::resource::GameEvents>().0.clear();
+
+            let action = world.write_resource::<::resource::LevelActions>().0.drain(..).next();
+            match action {
+                Some(LevelAction::Next) if tick + 1 == ticks => (),
+                None if tick + 1 < ticks => ::system::create_generator_spawns(&mut world),
+                _ => return Err(format!("part {} doesn't end at its last update", part)),
+            }
+        }
+    }
+
+    let duration = world.read_resource::<::resource::GameDuration>().0;
+    if duration != replay.duration {
+        return Err(format!("claimed duration {:?} instead of {:?}", replay.duration, duration));
+    }
+    Ok(())
+}